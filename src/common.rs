@@ -1,7 +1,156 @@
 use scrypto::prelude::*;
+use std::str::FromStr;
+
+// Version of the PeopleData model, stored in the people resource metadata; version 1 NFTs (LegacyPeopleData)
+// can be exchanged for version 2 ones through migrate_people
+pub const PEOPLE_DATA_VERSION: u8 = 2;
+
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Gender {
+    Unknown,
+    Male,
+    Female,
+}
+
+impl Gender {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Gender::Unknown => "unknown",
+            Gender::Male => "male",
+            Gender::Female => "female",
+        }
+    }
+}
+
+impl FromStr for Gender {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "unknown" => Ok(Gender::Unknown),
+            "male" => Ok(Gender::Male),
+            "female" => Ok(Gender::Female),
+            _ => Err(format!("Invalid gender: {}", value)),
+        }
+    }
+}
+
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MoodStatus {
+    Happy,
+    Normal,
+    Sad,
+    Depressed,
+}
+
+impl FromStr for MoodStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "happy" => Ok(MoodStatus::Happy),
+            "normal" => Ok(MoodStatus::Normal),
+            "sad" => Ok(MoodStatus::Sad),
+            "depressed" => Ok(MoodStatus::Depressed),
+            _ => Err(format!("Invalid mood status: {}", value)),
+        }
+    }
+}
+
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HealthStatus {
+    Healthy,
+    Weak,
+    Sick,
+    Critical,
+}
+
+impl HealthStatus {
+    // Radixians whose health is too low can't work or breed
+    pub fn is_low(&self) -> bool {
+        matches!(self, HealthStatus::Sick | HealthStatus::Critical)
+    }
+}
+
+impl FromStr for HealthStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "healthy" => Ok(HealthStatus::Healthy),
+            "weak" => Ok(HealthStatus::Weak),
+            "sick" => Ok(HealthStatus::Sick),
+            "critical" => Ok(HealthStatus::Critical),
+            _ => Err(format!("Invalid health status: {}", value)),
+        }
+    }
+}
+
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Schooling {
+    None,
+    Primary,
+    Secondary,
+    University,
+}
+
+impl Schooling {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Schooling::None => "none",
+            Schooling::Primary => "primary",
+            Schooling::Secondary => "secondary",
+            Schooling::University => "university",
+        }
+    }
+}
+
+impl FromStr for Schooling {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "none" => Ok(Schooling::None),
+            "primary" => Ok(Schooling::Primary),
+            "secondary" => Ok(Schooling::Secondary),
+            "university" => Ok(Schooling::University),
+            _ => Err(format!("Invalid schooling: {}", value)),
+        }
+    }
+}
 
 #[derive(ScryptoSbor, NonFungibleData)]
 pub struct PeopleData {
+    #[mutable]
+    pub name: String,
+    pub birth_date: Instant,
+    pub father: u64,
+    pub mother: u64,
+    // Empty until the random seed for the egg is received, then written only once
+    #[mutable]
+    pub genome: Vec<u8>,
+    #[mutable]
+    pub gender: Gender,
+    #[mutable]
+    pub occupation: String,
+    #[mutable]
+    pub partner: u64,
+    #[mutable]
+    pub mood_status: MoodStatus,
+    #[mutable]
+    pub health_status: HealthStatus,
+    #[mutable]
+    pub schooling: Schooling,
+    // Id of the house the Radixian lives in, zero if homeless
+    #[mutable]
+    pub residence: u64,
+    #[mutable]
+    pub key_image_url: Url,
+}
+
+// PeopleData version 1, with free strings instead of enums
+#[derive(ScryptoSbor, NonFungibleData)]
+pub struct LegacyPeopleData {
     #[mutable]
     pub name: String,
     pub birth_date: Instant,
@@ -26,15 +175,21 @@ pub struct PeopleData {
 #[derive(ScryptoSbor, NonFungibleData, Clone)]
 pub struct ObjectData {
     pub name: String,
-    #[mutable]
-    pub mortgaged: bool,
+    pub edition: u32,
+    pub max_supply: Option<u32>,
     #[mutable]
     pub rent_allowed: bool,
     #[mutable]
     pub daily_rent_price: u32,
     #[mutable]
     pub rent_to: u64,
+    #[mutable]
     pub key_image_url: Url,
+    // From 100 (new) to 0 (broken)
+    #[mutable]
+    pub condition: u8,
+    #[mutable]
+    pub condition_updated: Instant,
 }
 
 #[derive(ScryptoSbor, NonFungibleData)]
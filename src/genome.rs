@@ -0,0 +1,64 @@
+use scrypto::prelude::*;
+
+// Number of genes in a genome, every gene is one byte
+pub const GENOME_LENGTH: usize = 16;
+
+// A gene mutates when its mutation byte is below this threshold (about 3%)
+const MUTATION_THRESHOLD: u8 = 8;
+
+// A Radixian has a trait if the value of the gene is within the interval
+#[derive(ScryptoSbor, Clone)]
+pub struct TraitData {
+    pub gene: u8,
+    pub min_value: u8,
+    pub max_value: u8,
+    pub egg_image_url: Option<Url>,
+}
+
+impl TraitData {
+    pub fn is_expressed_by(&self, genome: &[u8]) -> bool {
+        match genome.get(self.gene as usize) {
+            None => false,
+            Some(value) => *value >= self.min_value && *value <= self.max_value,
+        }
+    }
+}
+
+// Genome of a Radixian with no known parents
+pub fn founder_genome(
+    random: &[u8],
+) -> Vec<u8> {
+    assert!(
+        random.len() >= GENOME_LENGTH,
+        "Not enough random bytes",
+    );
+
+    random[..GENOME_LENGTH].to_vec()
+}
+
+// Every gene is taken from one of the parents, the lowest bit of the first half of the random bytes
+// chooses which one; the second half decides whether the gene mutates into a random value
+pub fn child_genome(
+    father: &[u8],
+    mother: &[u8],
+    random: &[u8],
+) -> Vec<u8> {
+    assert!(
+        random.len() >= 2 * GENOME_LENGTH,
+        "Not enough random bytes",
+    );
+
+    (0..GENOME_LENGTH).map(|gene| {
+        let selector = random[gene];
+        let mutation = random[GENOME_LENGTH + gene];
+
+        if mutation < MUTATION_THRESHOLD {
+            selector.rotate_left(mutation as u32) ^ mutation
+        } else if selector & 1 == 0 {
+            father.get(gene).copied().unwrap_or(selector)
+        } else {
+            mother.get(gene).copied().unwrap_or(selector)
+        }
+    })
+    .collect()
+}
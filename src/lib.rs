@@ -1,3 +1,3 @@
 mod common;
+mod genome;
 pub mod radix_life;
-
@@ -1,14 +1,153 @@
 use scrypto::prelude::*;
 use std::ops::Deref;
+use std::str::FromStr;
 use crate::common::*;
+use crate::genome::*;
+
+// PeopleData string fields that the updater can modify through update_people_data; mood and health
+// only follow the needs
+const PEOPLE_STRING_FIELDS: [&str; 4] = [
+    "name",
+    "gender",
+    "occupation",
+    "schooling",
+];
+
+const SECONDS_PER_YEAR: i64 = 31536000;
+
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectCategory {
+    House,
+    Vehicle,
+    Tool,
+    Other,
+}
+
+#[derive(ScryptoSbor, Clone)]
+pub struct ObjectTypeData {
+    pub can_be_bought: bool,
+    pub can_be_mortgaged: bool,
+    pub can_be_rent: bool,
+    pub price: u32,
+    pub key_image_url: Url,
+    pub max_supply: Option<u32>,
+    pub minted: u32,
+    pub retired: bool,
+    pub daily_decay: u8,
+    pub category: ObjectCategory,
+    // Maximum number of residents of a house
+    pub capacity: u32,
+}
+
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq)]
+pub enum Need {
+    Food,
+    Rest,
+}
 
 #[derive(ScryptoSbor)]
-struct ObjectTypeData {
-    can_be_bought: bool,
-    can_be_mortgaged: bool,
-    can_be_rent: bool,
+struct ChoiceData {
+    price: u32,
+    restored_needs: Vec<Need>,
+    work: bool,
+    random: bool,
+    requires_home: bool,
+}
+
+// What to do once the random seed for a request is received
+#[derive(ScryptoSbor)]
+enum RandomRequest {
+    Genome {
+        people_id: u64,
+        father: u64,
+        mother: u64,
+    },
+    Choice {
+        choice: String,
+        people_id: u64,
+        number: u64,
+    },
+}
+
+#[derive(ScryptoSbor, Clone)]
+struct PeopleNeeds {
+    last_meal: Instant,
+    last_rest: Instant,
+}
+
+// A rentable object owned by the game itself, without an NFT
+#[derive(ScryptoSbor, Clone)]
+pub struct SystemObjectData {
+    pub name: String,
+    pub daily_rent_price: u32,
+    // Maximum number of residents, if it's a house
+    pub capacity: u32,
+    pub rent_to: u64,
+}
+
+// What RLC are minted or burned for
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CoinSource {
+    // Bought with XRD
+    Xrd,
+    // Deposited to or withdrawn from bank accounts
+    Bank,
+    // Loans and their repayments
+    Loan,
+    // Rewards sent by the backend
+    Reward,
+    // Second hand market purchases and proceeds
+    Market,
+    Object,
+    Choice,
+    Rent,
+    Repair,
+    Craft,
+}
+
+const COIN_SOURCES: [CoinSource; 10] = [
+    CoinSource::Xrd,
+    CoinSource::Bank,
+    CoinSource::Loan,
+    CoinSource::Reward,
+    CoinSource::Market,
+    CoinSource::Object,
+    CoinSource::Choice,
+    CoinSource::Rent,
+    CoinSource::Repair,
+    CoinSource::Craft,
+];
+
+#[derive(ScryptoSbor)]
+pub struct CoinSupply {
+    pub minted: Vec<(CoinSource, Decimal)>,
+    pub burned: Vec<(CoinSource, Decimal)>,
+    pub circulating: Decimal,
+}
+
+#[derive(ScryptoSbor, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LoanCollateral {
+    // Amount locked from the bank account of the borrower
+    BankBalance(u32),
+    // Id of the object in the collateral vault
+    Object(u64),
+}
+
+#[derive(ScryptoSbor, Clone)]
+pub struct LoanData {
+    pub people_id: u64,
+    pub collateral: LoanCollateral,
+    pub amount: u32,
+    // Still to be paid back, interest included
+    pub outstanding: u32,
+    pub due_date: Instant,
+}
+
+#[derive(ScryptoSbor)]
+struct RecipeData {
+    inputs: Vec<(String, u32)>,
     price: u32,
-    key_image_url: Url,
+    output: String,
 }
 
 #[derive(ScryptoSbor, ScryptoEvent)]
@@ -19,6 +158,11 @@ struct NewPeopleEvent {
     mother: u64,
 }
 
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct PeopleMigratedEvent {
+    people_id: u64,
+}
+
 #[derive(ScryptoSbor, ScryptoEvent)]
 struct NameEvent {
     people_id: u64,
@@ -29,10 +173,106 @@ struct NameEvent {
 struct NewObjectEvent {
     name: String,
     ids: Vec<u64>,
-    mortgaged: bool,
+    first_edition: u32,
+    owner: u64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct ObjectTypeAddedEvent {
+    name: String,
+    price: u32,
+    key_image_url: String,
+    can_be_bought: bool,
+    can_be_mortgaged: bool,
+    can_be_rent: bool,
+    max_supply: Option<u32>,
+    daily_decay: u8,
+    category: ObjectCategory,
+    capacity: u32,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct ObjectTypeUpdatedEvent {
+    name: String,
+    price: u32,
+    key_image_url: String,
+    can_be_bought: bool,
+    can_be_mortgaged: bool,
+    can_be_rent: bool,
+    retired: bool,
+    updated_object_ids: Vec<u64>,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct ObjectConditionEvent {
+    object_id: u64,
+    condition: u8,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct RepairEvent {
+    object_id: u64,
+    cost: u32,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct CraftedEvent {
+    recipe: String,
+    input_ids: Vec<u64>,
+    output_ids: Vec<u64>,
+    price: u32,
     owner: u64,
 }
 
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct ResidenceEvent {
+    people_id: u64,
+    object_id: u64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct RentIncomeEvent {
+    object_id: u64,
+    people_id: u64,
+    beneficiary: u64,
+    amount: u32,
+    paid_until: i64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct SystemObjectEvent {
+    object_id: u64,
+    name: String,
+    daily_rent_price: u32,
+    capacity: u32,
+    removed: bool,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct LoanEvent {
+    loan_id: u64,
+    people_id: u64,
+    collateral: LoanCollateral,
+    amount: u32,
+    outstanding: u32,
+    due_date: i64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct LoanRepaidEvent {
+    loan_id: u64,
+    amount: u32,
+    outstanding: u32,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct LoanLiquidatedEvent {
+    loan_id: u64,
+    people_id: u64,
+    collateral: LoanCollateral,
+    outstanding: u32,
+}
+
 #[derive(ScryptoSbor, ScryptoEvent)]
 struct BankDepositEvent {
     people_id: u64,
@@ -84,6 +324,28 @@ struct BankWithdrawEvent {
     people_id: u64,
 }
 
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct CoinsSentEvent {
+    account: ComponentAddress,
+    amount: u32,
+    reason: u16,
+    people_id: u64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct BankTransferEvent {
+    from_people_id: u64,
+    to_people_id: u64,
+    amount: u32,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct InterestAccruedEvent {
+    people_id: u64,
+    amount: u32,
+    balance: u32,
+}
+
 #[derive(ScryptoSbor, ScryptoEvent)]
 struct SoldPeopleEvent {
     people_id: u64,
@@ -96,10 +358,78 @@ struct BoughtPeopleEvent {
     people_id: u64,
 }
 
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct HeirEvent {
+    people_id: u64,
+    heir: u64,
+}
+
+#[derive(ScryptoSbor)]
+struct PeopleFieldChange {
+    field: String,
+    old_value: String,
+    new_value: String,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct PeopleUpdatedEvent {
+    people_id: u64,
+    changes: Vec<PeopleFieldChange>,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct NeedsChangedEvent {
+    people_id: u64,
+    last_meal: i64,
+    last_rest: i64,
+    health_status: HealthStatus,
+    mood_status: MoodStatus,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct RandomRequestEvent {
+    key: u32,
+    people_id: u64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct GenomeEvent {
+    key: u32,
+    people_id: u64,
+    seed: Vec<u8>,
+    genome: Vec<u8>,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct RandomChoiceEvent {
+    key: u32,
+    choice: String,
+    people_id: u64,
+    number: u64,
+    seed: Vec<u8>,
+    result: u64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct ObjectOwnerEvent {
+    object_id: u64,
+    owner: u64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent)]
+struct InheritanceEvent {
+    people_id: u64,
+    heir: u64,
+    amount: u32,
+    object_ids: Vec<u64>,
+    loan_ids: Vec<u64>,
+}
+
 #[blueprint]
 #[events(
     NewPeopleEvent,
     NameEvent,
+    PeopleMigratedEvent,
     NewObjectEvent,
     BankDepositEvent,
     AllowRentEvent,
@@ -109,8 +439,30 @@ struct BoughtPeopleEvent {
     BoughtObjectEvent,
     ChoiceEvent,
     BankWithdrawEvent,
+    InterestAccruedEvent,
+    BankTransferEvent,
+    CoinsSentEvent,
     SoldPeopleEvent,
     BoughtPeopleEvent,
+    HeirEvent,
+    InheritanceEvent,
+    ObjectOwnerEvent,
+    PeopleUpdatedEvent,
+    NeedsChangedEvent,
+    RandomRequestEvent,
+    GenomeEvent,
+    RandomChoiceEvent,
+    ObjectTypeAddedEvent,
+    ObjectTypeUpdatedEvent,
+    ObjectConditionEvent,
+    RepairEvent,
+    CraftedEvent,
+    ResidenceEvent,
+    RentIncomeEvent,
+    SystemObjectEvent,
+    LoanEvent,
+    LoanRepaidEvent,
+    LoanLiquidatedEvent,
 )]
 #[types(
     String,
@@ -121,6 +473,18 @@ struct BoughtPeopleEvent {
     SoldObjectReceipt,
     SoldPeopleReceipt,
     u32,
+    Vec<u64>,
+    Instant,
+    Vec<String>,
+    ChoiceData,
+    PeopleNeeds,
+    TraitData,
+    RandomRequest,
+    RecipeData,
+    SystemObjectData,
+    LoanData,
+    CoinSource,
+    Decimal,
 )]
 mod radix_life {
 
@@ -134,12 +498,31 @@ mod radix_life {
             update_coin_xrd_price => restrict_to: [OWNER];
             add_choice => restrict_to: [OWNER];
             update_object_type => restrict_to: [OWNER];
+            update_object_type_image => restrict_to: [OWNER];
+            retire_object_type => restrict_to: [OWNER];
+            add_recipe => restrict_to: [OWNER];
+            set_people_field_domain => restrict_to: [OWNER];
+            update_needs_intervals => restrict_to: [OWNER];
+            add_trait => restrict_to: [OWNER];
+            set_random_component => restrict_to: [OWNER];
+            update_inbreeding_generations => restrict_to: [OWNER];
+            update_loan_terms => restrict_to: [OWNER];
+            update_savings_apr => restrict_to: [OWNER];
+            update_reward_caps => restrict_to: [OWNER];
+            set_price_oracle => restrict_to: [OWNER];
+            set_legacy_people_resource => restrict_to: [OWNER];
 
             new_egg => restrict_to: [updater];
             new_object => restrict_to: [updater];
             update_people_data => restrict_to: [updater];
             update_object_data => restrict_to: [updater];
             send_coins => restrict_to: [updater];
+            send_coins_batch => restrict_to: [updater];
+            new_objects_batch => restrict_to: [updater];
+            execute_inheritance => restrict_to: [updater];
+            add_system_object => restrict_to: [updater];
+            update_system_object => restrict_to: [updater];
+            remove_system_object => restrict_to: [updater];
 
             buy_egg => PUBLIC;
             buy_objects => PUBLIC;
@@ -147,10 +530,20 @@ mod radix_life {
             give_name => PUBLIC;
             deposit_to_bank_account => PUBLIC;
             withdraw_from_bank_account => PUBLIC;
-            mortgage => PUBLIC;
+            bank_transfer => PUBLIC;
+            get_coin_supply => PUBLIC;
+            get_coin_xrd_price => PUBLIC;
+            migrate_people => PUBLIC;
+            borrow => PUBLIC;
+            borrow_with_object => PUBLIC;
+            repay => PUBLIC;
+            liquidate => PUBLIC;
+            get_loan => PUBLIC;
+            get_loans => PUBLIC;
             allow_rent => PUBLIC;
             rent => PUBLIC;
             terminate_rent => PUBLIC;
+            evict => PUBLIC;
             sell_object => PUBLIC;
             buy_used_object => PUBLIC;
             close_object_sale => PUBLIC;
@@ -158,6 +551,27 @@ mod radix_life {
             sell_people => PUBLIC;
             buy_people => PUBLIC;
             close_people_sale => PUBLIC;
+            set_heir => PUBLIC;
+            retire_people => PUBLIC;
+            get_bank_balance => PUBLIC;
+            transfer_object_owner => PUBLIC;
+            get_object_owner => PUBLIC;
+            get_owned_objects => PUBLIC;
+            refresh_needs => PUBLIC;
+            get_traits => PUBLIC;
+            random_callback => PUBLIC;
+            get_children => PUBLIC;
+            get_ancestors => PUBLIC;
+            get_descendants => PUBLIC;
+            get_object_type => PUBLIC;
+            list_object_types => PUBLIC;
+            repair => PUBLIC;
+            craft => PUBLIC;
+            move_in => PUBLIC;
+            move_out => PUBLIC;
+            get_residents => PUBLIC;
+            pay_rent => PUBLIC;
+            get_system_object => PUBLIC;
         }
     }
 
@@ -174,7 +588,7 @@ mod radix_life {
         account_locker: Global<AccountLocker>,
         xrd_vault: Vault,
         sold_objects_resource_manager: NonFungibleResourceManager,
-        choices: KeyValueStore<String, u32>,
+        choices: KeyValueStore<String, ChoiceData>,
         people_vault: NonFungibleVault,
         people_prices: KeyValueStore<u64, u32>,
         sold_people_resource_manager: NonFungibleResourceManager,
@@ -182,6 +596,58 @@ mod radix_life {
         object_types: KeyValueStore<String, ObjectTypeData>,
         used_objects_vault: NonFungibleVault,
         last_receipt_id: u64,
+        bank_accounts: KeyValueStore<u64, u32>,
+        heirs: KeyValueStore<u64, u64>,
+        children: KeyValueStore<u64, Vec<u64>>,
+        object_owners: KeyValueStore<u64, u64>,
+        owned_objects: KeyValueStore<u64, Vec<u64>>,
+        retired_people: KeyValueStore<u64, Instant>,
+        people_field_domains: KeyValueStore<String, Vec<String>>,
+        people_needs: KeyValueStore<u64, PeopleNeeds>,
+        meal_interval: i64,
+        rest_interval: i64,
+        traits: KeyValueStore<String, TraitData>,
+        trait_names: Vec<String>,
+        random_component: Option<Global<AnyComponent>>,
+        random_requests: KeyValueStore<u32, RandomRequest>,
+        last_random_key: u32,
+        inbreeding_generations: u8,
+        object_type_names: Vec<String>,
+        recipes: KeyValueStore<String, RecipeData>,
+        residents: KeyValueStore<u64, Vec<u64>>,
+        rent_beneficiaries: KeyValueStore<u64, u64>,
+        rent_paid_until: KeyValueStore<u64, Instant>,
+        system_objects: KeyValueStore<u64, SystemObjectData>,
+        loans: KeyValueStore<u64, LoanData>,
+        last_loan_id: u64,
+        people_loans: KeyValueStore<u64, Vec<u64>>,
+        collateral_vault: NonFungibleVault,
+        // Interest percentage over the whole term of a loan
+        loan_interest: u8,
+        loan_term: i64,
+        loan_to_value: u8,
+        savings_apr: Decimal,
+        interest_accrued_at: KeyValueStore<u64, Instant>,
+        // Fraction of a coin of interest accrued but not credited yet
+        interest_remainders: KeyValueStore<u64, Decimal>,
+        minted_coins: KeyValueStore<CoinSource, Decimal>,
+        burned_coins: KeyValueStore<CoinSource, Decimal>,
+        // Maximum amount of coins that send_coins can mint in a single call, in an epoch and in a day
+        reward_call_cap: Option<u32>,
+        epoch_reward_cap: Option<u32>,
+        daily_reward_cap: Option<u32>,
+        reward_epoch: u64,
+        rewarded_this_epoch: u32,
+        reward_day: i64,
+        rewarded_today: u32,
+        // Any component exposing get_price() -> (Decimal, Instant) with the price of XRD in USD
+        price_oracle: Option<Global<AnyComponent>>,
+        coin_usd_price: Decimal,
+        oracle_max_age: i64,
+        min_coin_xrd_price: Decimal,
+        max_coin_xrd_price: Decimal,
+        // Version 1 Radixians exchanged through migrate_people
+        legacy_people_vault: Option<NonFungibleVault>,
     }
 
     impl RadixLife {
@@ -231,6 +697,7 @@ mod radix_life {
                 },
                 init {
                     "name" => "Radixian", updatable;
+                    "data_version" => PEOPLE_DATA_VERSION, updatable;
                 }
             ))
             .mint_roles(mint_roles!(
@@ -261,8 +728,9 @@ mod radix_life {
                     "name" => "RadixLife coin", updatable;
                 }
             ))
+            // Only the component can mint coins, so that every mint is accounted for
             .mint_roles(mint_roles!(
-                minter => component_or_updater_rule.clone();
+                minter => rule!(require(global_caller(component_address)));
                 minter_updater => rule!(require(owner_badge_address));
             ))
             .burn_roles(burn_roles!(
@@ -273,7 +741,7 @@ mod radix_life {
 
             let account_locker = Blueprint::<AccountLocker>::instantiate(
                 OwnerRole::Updatable(rule!(require(owner_badge_address))),  // owner_role
-                component_or_updater_rule,                                  // storer_role
+                component_or_updater_rule.clone(),                          // storer_role
                 rule!(require(owner_badge_address)),                        // storer_updater_role
                 rule!(deny_all),                                            // recoverer_role
                 rule!(require(owner_badge_address)),                        // recoverer_updater_role
@@ -351,7 +819,7 @@ mod radix_life {
                 non_fungible_data_updater_updater => rule!(require(owner_badge_address));
             ))
             .burn_roles(burn_roles!(
-                burner => rule!(require(updater_badge_address));
+                burner => component_or_updater_rule;
                 burner_updater => rule!(require(owner_badge_address));
             ))
             .recall_roles(recall_roles!(
@@ -381,6 +849,53 @@ mod radix_life {
                 object_types: KeyValueStore::new_with_registered_type(),
                 used_objects_vault: NonFungibleVault::new(object_resource_manager.address()),
                 last_receipt_id: 0,
+                bank_accounts: KeyValueStore::new_with_registered_type(),
+                heirs: KeyValueStore::new_with_registered_type(),
+                children: KeyValueStore::new_with_registered_type(),
+                object_owners: KeyValueStore::new_with_registered_type(),
+                owned_objects: KeyValueStore::new_with_registered_type(),
+                retired_people: KeyValueStore::new_with_registered_type(),
+                people_field_domains: KeyValueStore::new_with_registered_type(),
+                people_needs: KeyValueStore::new_with_registered_type(),
+                meal_interval: 86400,
+                rest_interval: 86400,
+                traits: KeyValueStore::new_with_registered_type(),
+                trait_names: vec![],
+                random_component: None,
+                random_requests: KeyValueStore::new_with_registered_type(),
+                last_random_key: 0,
+                inbreeding_generations: 3,
+                object_type_names: vec![],
+                recipes: KeyValueStore::new_with_registered_type(),
+                residents: KeyValueStore::new_with_registered_type(),
+                rent_beneficiaries: KeyValueStore::new_with_registered_type(),
+                rent_paid_until: KeyValueStore::new_with_registered_type(),
+                system_objects: KeyValueStore::new_with_registered_type(),
+                loans: KeyValueStore::new_with_registered_type(),
+                last_loan_id: 0,
+                people_loans: KeyValueStore::new_with_registered_type(),
+                collateral_vault: NonFungibleVault::new(object_resource_manager.address()),
+                loan_interest: 10,
+                loan_term: 2592000,
+                loan_to_value: 50,
+                savings_apr: Decimal::ZERO,
+                interest_accrued_at: KeyValueStore::new_with_registered_type(),
+                interest_remainders: KeyValueStore::new_with_registered_type(),
+                minted_coins: KeyValueStore::new_with_registered_type(),
+                burned_coins: KeyValueStore::new_with_registered_type(),
+                reward_call_cap: None,
+                epoch_reward_cap: None,
+                daily_reward_cap: None,
+                reward_epoch: 0,
+                rewarded_this_epoch: 0,
+                reward_day: 0,
+                rewarded_today: 0,
+                price_oracle: None,
+                coin_usd_price: Decimal::ZERO,
+                oracle_max_age: 0,
+                min_coin_xrd_price: Decimal::ZERO,
+                max_coin_xrd_price: Decimal::ZERO,
+                legacy_people_vault: None,
             }
             .instantiate()
             .prepare_to_globalize(OwnerRole::Updatable(rule!(require(owner_badge_address))))
@@ -399,19 +914,78 @@ mod radix_life {
             can_be_bought: bool,
             can_be_mortgaged: bool,
             can_be_rent: bool,
+            max_supply: Option<u32>,
+            daily_decay: u8,
+            category: ObjectCategory,
+            capacity: u32,
         ) {
+            assert!(
+                daily_decay <= 100,
+                "Invalid daily decay",
+            );
+            assert!(
+                category != ObjectCategory::House || capacity > 0,
+                "A house must have room for at least one resident",
+            );
+            assert!(
+                self.object_types.get(&name).is_none(),
+                "Object type already exists",
+            );
+            self.object_type_names.push(name.clone());
+
             self.object_types.insert(
-                name,
+                name.clone(),
                 ObjectTypeData {
                     can_be_bought: can_be_bought,
                     can_be_mortgaged: can_be_mortgaged,
                     can_be_rent: can_be_rent,
                     price: price,
-                    key_image_url: UncheckedUrl(key_image_url),
+                    key_image_url: UncheckedUrl(key_image_url.clone()),
+                    max_supply: max_supply,
+                    minted: 0,
+                    retired: false,
+                    daily_decay: daily_decay,
+                    category: category,
+                    capacity: capacity,
+                }
+            );
+
+            Runtime::emit_event(
+                ObjectTypeAddedEvent {
+                    name: name,
+                    price: price,
+                    key_image_url: key_image_url,
+                    can_be_bought: can_be_bought,
+                    can_be_mortgaged: can_be_mortgaged,
+                    can_be_rent: can_be_rent,
+                    max_supply: max_supply,
+                    daily_decay: daily_decay,
+                    category: category,
+                    capacity: capacity,
                 }
             );
         }
 
+        pub fn get_object_type(
+            &self,
+            name: String,
+        ) -> Option<ObjectTypeData> {
+            self.object_types.get(&name).map(|object_type| object_type.deref().clone())
+        }
+
+        // Object types in the order they were added
+        pub fn list_object_types(
+            &self,
+            offset: u32,
+            limit: u32,
+        ) -> Vec<(String, ObjectTypeData)> {
+            self.object_type_names.iter()
+                .skip(offset as usize)
+                .take(limit as usize)
+                .map(|name| (name.clone(), self.object_types.get(name).unwrap().deref().clone()))
+                .collect()
+        }
+
         pub fn withdraw_xrd(&mut self) -> Bucket {
             self.xrd_vault.take_all()
         }
@@ -427,6 +1001,60 @@ mod radix_life {
             self.coin_xrd_price = coin_xrd_price;
         }
 
+        // When an oracle is set, the coin is pegged to coin_usd_price; the stored coin_xrd_price is still
+        // used if the oracle price is older than oracle_max_age seconds or out of the bounds
+        pub fn set_price_oracle(
+            &mut self,
+            price_oracle: Option<ComponentAddress>,
+            coin_usd_price: Decimal,
+            oracle_max_age: i64,
+            min_coin_xrd_price: Decimal,
+            max_coin_xrd_price: Decimal,
+        ) {
+            if price_oracle.is_some() {
+                assert!(
+                    coin_usd_price > Decimal::ZERO,
+                    "Coin price must be bigger than zero",
+                );
+                assert!(
+                    oracle_max_age > 0,
+                    "Max age must be bigger than zero",
+                );
+                assert!(
+                    min_coin_xrd_price > Decimal::ZERO && min_coin_xrd_price <= max_coin_xrd_price,
+                    "Invalid bounds",
+                );
+            }
+
+            self.price_oracle = price_oracle.map(Global::<AnyComponent>::from);
+            self.coin_usd_price = coin_usd_price;
+            self.oracle_max_age = oracle_max_age;
+            self.min_coin_xrd_price = min_coin_xrd_price;
+            self.max_coin_xrd_price = max_coin_xrd_price;
+        }
+
+        pub fn get_coin_xrd_price(&self) -> Decimal {
+            let price_oracle = match self.price_oracle {
+                None => return self.coin_xrd_price,
+                Some(price_oracle) => price_oracle,
+            };
+
+            let (xrd_usd_price, updated) = price_oracle.call_raw::<(Decimal, Instant)>(
+                "get_price",
+                scrypto_args!(),
+            );
+            let age = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch - updated.seconds_since_unix_epoch;
+            if xrd_usd_price <= Decimal::ZERO || age < 0 || age > self.oracle_max_age {
+                return self.coin_xrd_price;
+            }
+
+            let coin_xrd_price = self.coin_usd_price / xrd_usd_price;
+            match coin_xrd_price >= self.min_coin_xrd_price && coin_xrd_price <= self.max_coin_xrd_price {
+                true => coin_xrd_price,
+                false => self.coin_xrd_price,
+            }
+        }
+
         fn mint_egg(
             &mut self,
             father: u64,
@@ -445,104 +1073,752 @@ mod radix_life {
                 }
             );
 
-            self.people_resource_manager.mint_non_fungible(
+            self.people_needs.insert(
+                self.last_people_id,
+                PeopleNeeds {
+                    last_meal: birth_date,
+                    last_rest: birth_date,
+                }
+            );
+
+            if father != 0 {
+                RadixLife::add_to_list(&self.children, father, self.last_people_id);
+            }
+            if mother != 0 {
+                RadixLife::add_to_list(&self.children, mother, self.last_people_id);
+            }
+
+            let egg_bucket = self.people_resource_manager.mint_non_fungible(
                 &NonFungibleLocalId::integer(self.last_people_id.into()),
                 PeopleData {
                     name: "".to_string(),
                     birth_date: birth_date,
                     father: father,
                     mother: mother,
-                    gender: "unknown".to_string(),
+                    genome: vec![],
+                    gender: Gender::Unknown,
                     occupation: "unemployed".to_string(),
                     partner: 0,
-                    mood_status: "normal".to_string(),
-                    health_status: "healthy".to_string(),
-                    schooling: "none".to_string(),
+                    mood_status: MoodStatus::Happy,
+                    health_status: HealthStatus::Healthy,
+                    schooling: Schooling::None,
+                    residence: 0,
                     key_image_url: self.egg_image_url.clone(),
                 }
-            )
-        }
-
-        pub fn new_egg(
-            &mut self,
-            father: u64,
-            mother: u64,
-            account: Global<Account>,
-        ) {
-            let egg_bucket = self.mint_egg(father, mother);
-
-            self.account_locker.store(
-                account,
-                egg_bucket.into(),
-                true,
-            );
-        }
-
-        pub fn new_object(
-            &mut self,
-            name: String,
-            mortgaged: bool,
-            account: Global<Account>,
-            owner: u64,
-        ) {
-            let object_type = self.object_types.get(&name).expect("Object not found");
-
-            self.last_object_id += 1;
-
-            let object_bucket = self.object_resource_manager.mint_non_fungible(
-                &NonFungibleLocalId::integer(self.last_object_id.into()),
-                ObjectData {
-                    name: name.clone(),
-                    mortgaged: mortgaged,
-                    rent_allowed: false,
-                    daily_rent_price: 0,
-                    rent_to: 0,
-                    key_image_url: object_type.key_image_url.clone(),
-                }
             );
 
-            Runtime::emit_event(
-                NewObjectEvent {
-                    name: name,
-                    ids: vec![self.last_object_id],
-                    mortgaged: mortgaged,
-                    owner: owner,
+            self.request_random(
+                self.last_people_id,
+                RandomRequest::Genome {
+                    people_id: self.last_people_id,
+                    father: father,
+                    mother: mother,
                 }
             );
 
-            self.account_locker.store(
-                account,
-                object_bucket.into(),
-                true,
-            );
+            egg_bucket
         }
 
-        pub fn buy_egg(
+        // New eggs get ids above last_legacy_people_id, so that migrated Radixians can keep their ids and
+        // their family links
+        pub fn set_legacy_people_resource(
             &mut self,
-            mut xrd_bucket: Bucket,
-        ) -> (
-            NonFungibleBucket,
-            Bucket,
+            legacy_people_address: ResourceAddress,
+            last_legacy_people_id: u64,
         ) {
             assert!(
-                xrd_bucket.resource_address() == XRD,
-                "Wrong coin",
+                self.legacy_people_vault.is_none(),
+                "Legacy resource already set",
             );
             assert!(
-                xrd_bucket.amount() >= self.egg_xrd_price,
-                "Insufficient amount",
+                legacy_people_address != self.people_resource_manager.address(),
+                "Wrong resource",
             );
-            self.xrd_vault.put(xrd_bucket.take(self.egg_xrd_price));
-
+            // Radixians minted before would have ids clashing with the legacy ones
             assert!(
-                self.eggs_on_sale > 0,
-                "Sale ended",
+                self.last_people_id == 0,
+                "Radixians already minted",
             );
-            self.eggs_on_sale -= 1;
 
-            (
-                self.mint_egg(0, 0),
-                xrd_bucket,
+            self.legacy_people_vault = Some(NonFungibleVault::new(legacy_people_address));
+            self.last_people_id = last_legacy_people_id;
+        }
+
+        // Exchanges version 1 Radixians for version 2 ones with the same ids; the string fields are
+        // converted to the new enums, unknown values are reset to the defaults. The legacy NFTs are
+        // kept by the component
+        pub fn migrate_people(
+            &mut self,
+            legacy_people_bucket: NonFungibleBucket,
+        ) -> NonFungibleBucket {
+            let legacy_people_address = self.legacy_people_vault.as_ref().expect("No legacy resource").resource_address();
+            assert!(
+                legacy_people_bucket.resource_address() == legacy_people_address,
+                "Wrong NFT",
+            );
+
+            // Version 1 Radixians had no needs, they start fed and rested
+            let now = Clock::current_time_rounded_to_seconds();
+            let mut people_bucket = NonFungibleBucket::new(self.people_resource_manager.address());
+            for non_fungible in legacy_people_bucket.non_fungibles::<LegacyPeopleData>() {
+                let people_id = RadixLife::get_u64_id(non_fungible.local_id());
+                let legacy_data = non_fungible.data();
+                assert!(
+                    !self.people_resource_manager.non_fungible_exists(non_fungible.local_id()),
+                    "Radixian already migrated",
+                );
+
+                self.people_needs.insert(
+                    people_id,
+                    PeopleNeeds {
+                        last_meal: now,
+                        last_rest: now,
+                    }
+                );
+                if legacy_data.father != 0 {
+                    RadixLife::add_to_list(&self.children, legacy_data.father, people_id);
+                }
+                if legacy_data.mother != 0 {
+                    RadixLife::add_to_list(&self.children, legacy_data.mother, people_id);
+                }
+
+                people_bucket.put(
+                    self.people_resource_manager.mint_non_fungible(
+                        non_fungible.local_id(),
+                        PeopleData {
+                            name: legacy_data.name,
+                            birth_date: legacy_data.birth_date,
+                            father: legacy_data.father,
+                            mother: legacy_data.mother,
+                            genome: vec![],
+                            gender: Gender::from_str(&legacy_data.gender).unwrap_or(Gender::Unknown),
+                            occupation: legacy_data.occupation,
+                            partner: legacy_data.partner,
+                            mood_status: MoodStatus::from_str(&legacy_data.mood_status).unwrap_or(MoodStatus::Normal),
+                            health_status: HealthStatus::from_str(&legacy_data.health_status).unwrap_or(HealthStatus::Healthy),
+                            schooling: Schooling::from_str(&legacy_data.schooling).unwrap_or(Schooling::None),
+                            residence: 0,
+                            key_image_url: legacy_data.key_image_url,
+                        }
+                    )
+                );
+
+                Runtime::emit_event(
+                    PeopleMigratedEvent {
+                        people_id: people_id,
+                    }
+                );
+
+                // Version 1 Radixians had no genome, they get a founder one
+                self.request_random(
+                    people_id,
+                    RandomRequest::Genome {
+                        people_id: people_id,
+                        father: 0,
+                        mother: 0,
+                    }
+                );
+            }
+
+            self.legacy_people_vault.as_mut().unwrap().put(legacy_people_bucket);
+
+            people_bucket
+        }
+
+        // The randomness component must call back random_callback(key, seed). There's no fallback: a seed
+        // generated in this transaction could be previewed and the transaction retried until it's favourable
+        fn request_random(
+            &mut self,
+            people_id: u64,
+            request: RandomRequest,
+        ) {
+            self.last_random_key += 1;
+            let key = self.last_random_key;
+
+            Runtime::emit_event(
+                RandomRequestEvent {
+                    key: key,
+                    people_id: people_id,
+                }
+            );
+
+            let random_component = self.random_component.expect("No randomness component");
+            self.random_requests.insert(key, request);
+
+            random_component.call_raw::<()>(
+                "request_random",
+                scrypto_args!(
+                    Runtime::global_address(),
+                    "random_callback".to_string(),
+                    key
+                ),
+            );
+        }
+
+        pub fn random_callback(
+            &mut self,
+            key: u32,
+            seed: Vec<u8>,
+        ) {
+            let random_component = self.random_component.expect("No randomness component");
+            Runtime::assert_access_rule(rule!(require(global_caller(random_component.address()))));
+
+            let request = self.random_requests.remove(&key).expect("Request not found");
+
+            self.resolve_random(key, request, seed);
+        }
+
+        fn resolve_random(
+            &self,
+            key: u32,
+            request: RandomRequest,
+            seed: Vec<u8>,
+        ) {
+            assert!(
+                seed.len() >= 2 * GENOME_LENGTH,
+                "Seed too short",
+            );
+
+            match request {
+                RandomRequest::Genome { people_id, father, mother } => {
+                    let genome = match (father, mother) {
+                        (0, 0) => founder_genome(&seed),
+                        _ => child_genome(
+                            &self.get_genome(father),
+                            &self.get_genome(mother),
+                            &seed,
+                        ),
+                    };
+
+                    let id = NonFungibleLocalId::integer(people_id.into());
+                    let people_data = self.people_resource_manager.get_non_fungible_data::<PeopleData>(&id);
+                    assert!(
+                        people_data.genome.is_empty(),
+                        "Genome already set",
+                    );
+                    self.people_resource_manager.update_non_fungible_data(&id, "genome", genome.clone());
+
+                    // Migrated Radixians keep their own image
+                    if people_data.key_image_url == self.egg_image_url {
+                        self.people_resource_manager.update_non_fungible_data(&id, "key_image_url", self.get_egg_image_url(&genome));
+                    }
+
+                    Runtime::emit_event(
+                        GenomeEvent {
+                            key: key,
+                            people_id: people_id,
+                            seed: seed,
+                            genome: genome,
+                        }
+                    );
+                },
+                RandomRequest::Choice { choice, people_id, number } => {
+                    let result = u64::from_le_bytes(seed[..8].try_into().unwrap());
+
+                    Runtime::emit_event(
+                        RandomChoiceEvent {
+                            key: key,
+                            choice: choice,
+                            people_id: people_id,
+                            number: number,
+                            seed: seed,
+                            result: result,
+                        }
+                    );
+                },
+            }
+        }
+
+        pub fn set_random_component(
+            &mut self,
+            random_component: Option<ComponentAddress>,
+        ) {
+            self.random_component = random_component.map(Global::<AnyComponent>::from);
+        }
+
+        // Zero is an unknown Radixian with no genes; an unknown parent is replaced by random genes
+        fn get_genome(
+            &self,
+            parent: u64,
+        ) -> Vec<u8> {
+            match parent {
+                0 => vec![],
+                _ => self.people_resource_manager.get_non_fungible_data::<PeopleData>(
+                    &NonFungibleLocalId::integer(parent.into())
+                ).genome,
+            }
+        }
+
+        // The image of the first registered trait expressed by the genome, or the default egg image
+        fn get_egg_image_url(
+            &self,
+            genome: &[u8],
+        ) -> Url {
+            for name in self.trait_names.iter() {
+                let trait_data = self.traits.get(name).unwrap();
+                if trait_data.is_expressed_by(genome) {
+                    if let Some(egg_image_url) = &trait_data.egg_image_url {
+                        return egg_image_url.clone();
+                    }
+                }
+            }
+
+            self.egg_image_url.clone()
+        }
+
+        pub fn add_trait(
+            &mut self,
+            name: String,
+            gene: u8,
+            min_value: u8,
+            max_value: u8,
+            egg_image_url: Option<String>,
+        ) {
+            assert!(
+                (gene as usize) < GENOME_LENGTH,
+                "Invalid gene",
+            );
+            assert!(
+                min_value <= max_value,
+                "Invalid interval",
+            );
+            assert!(
+                self.traits.get(&name).is_none(),
+                "Trait already exists",
+            );
+
+            self.traits.insert(
+                name.clone(),
+                TraitData {
+                    gene: gene,
+                    min_value: min_value,
+                    max_value: max_value,
+                    egg_image_url: egg_image_url.map(UncheckedUrl),
+                }
+            );
+            self.trait_names.push(name);
+        }
+
+        pub fn get_traits(
+            &self,
+            people_id: u64,
+        ) -> Vec<String> {
+            let genome = self.get_genome(people_id);
+
+            self.trait_names.iter()
+                .filter(|name| self.traits.get(name).unwrap().is_expressed_by(&genome))
+                .cloned()
+                .collect()
+        }
+
+        pub fn update_inbreeding_generations(
+            &mut self,
+            inbreeding_generations: u8,
+        ) {
+            self.inbreeding_generations = inbreeding_generations;
+        }
+
+        // Known ancestors up to the given number of generations, closest generations first
+        fn collect_ancestors(
+            &self,
+            people_id: u64,
+            generations: u8,
+        ) -> Vec<u64> {
+            let mut ancestors: Vec<u64> = vec![];
+            let mut generation = vec![people_id];
+
+            for _ in 0..generations {
+                let mut parents: Vec<u64> = vec![];
+                for id in generation.iter() {
+                    let people_data = self.people_resource_manager.get_non_fungible_data::<PeopleData>(
+                        &NonFungibleLocalId::integer((*id).into())
+                    );
+                    for parent in [people_data.father, people_data.mother] {
+                        if parent != 0 && !ancestors.contains(&parent) {
+                            ancestors.push(parent);
+                            parents.push(parent);
+                        }
+                    }
+                }
+                if parents.is_empty() {
+                    break;
+                }
+                generation = parents;
+            }
+
+            ancestors
+        }
+
+        pub fn get_children(
+            &self,
+            people_id: u64,
+        ) -> Vec<u64> {
+            match self.children.get(&people_id) {
+                None => vec![],
+                Some(children) => children.deref().clone(),
+            }
+        }
+
+        pub fn get_ancestors(
+            &self,
+            people_id: u64,
+            generations: u8,
+        ) -> Vec<u64> {
+            assert!(
+                self.people_resource_manager.non_fungible_exists(&NonFungibleLocalId::integer(people_id.into())),
+                "Radixian not found",
+            );
+
+            self.collect_ancestors(people_id, generations)
+        }
+
+        // Descendants up to the given number of generations, closest generations first
+        pub fn get_descendants(
+            &self,
+            people_id: u64,
+            generations: u8,
+        ) -> Vec<u64> {
+            let mut descendants: Vec<u64> = vec![];
+            let mut generation = vec![people_id];
+
+            for _ in 0..generations {
+                let mut children: Vec<u64> = vec![];
+                for id in generation.iter() {
+                    for child in self.get_children(*id) {
+                        if !descendants.contains(&child) {
+                            descendants.push(child);
+                            children.push(child);
+                        }
+                    }
+                }
+                if children.is_empty() {
+                    break;
+                }
+                generation = children;
+            }
+
+            descendants
+        }
+
+        pub fn new_egg(
+            &mut self,
+            father: u64,
+            mother: u64,
+            account: Global<Account>,
+        ) {
+            assert!(
+                father != mother || father == 0,
+                "Parents must be different",
+            );
+            for parent in [father, mother] {
+                if parent != 0 {
+                    assert!(
+                        self.people_resource_manager.non_fungible_exists(&NonFungibleLocalId::integer(parent.into())),
+                        "Parent not found",
+                    );
+                    assert!(
+                        !self.update_people_needs(parent, &[]).is_low(),
+                        "Parent health too low",
+                    );
+                    // The genome of a parent waiting for its random seed isn't known yet
+                    assert!(
+                        !self.get_genome(parent).is_empty(),
+                        "Parent genome not set",
+                    );
+                }
+            }
+            if father != 0 && mother != 0 {
+                let mut father_line = self.collect_ancestors(father, self.inbreeding_generations);
+                father_line.push(father);
+                let mut mother_line = self.collect_ancestors(mother, self.inbreeding_generations);
+                mother_line.push(mother);
+
+                assert!(
+                    !father_line.iter().any(|id| mother_line.contains(id)),
+                    "Parents are too closely related",
+                );
+            }
+
+            let egg_bucket = self.mint_egg(father, mother);
+
+            self.account_locker.store(
+                account,
+                egg_bucket.into(),
+                true,
+            );
+        }
+
+        pub fn new_object(
+            &mut self,
+            name: String,
+            account: Global<Account>,
+            owner: u64,
+        ) {
+            self.check_new_owner(owner);
+
+            let object_bucket = self.mint_objects(name, 1, owner);
+
+            self.account_locker.store(
+                account,
+                object_bucket.into(),
+                true,
+            );
+        }
+
+        // Every object is minted separately, then a single airdrop delivers all of them
+        pub fn new_objects_batch(
+            &mut self,
+            objects: Vec<(String, Global<Account>, u64)>,
+        ) {
+            let mut claimants: IndexMap<Global<Account>, IndexSet<NonFungibleLocalId>> = index_map_new();
+            let mut objects_bucket = NonFungibleBucket::new(self.object_resource_manager.address());
+            for (name, account, owner) in objects {
+                self.check_new_owner(owner);
+
+                let object_bucket = self.mint_objects(name, 1, owner);
+                claimants.entry(account)
+                    .or_insert_with(index_set_new)
+                    .extend(object_bucket.non_fungible_local_ids());
+                objects_bucket.put(object_bucket);
+            }
+
+            self.account_locker.airdrop(
+                claimants.into_iter()
+                    .map(|(account, ids)| (account, ResourceSpecifier::NonFungible(ids)))
+                    .collect(),
+                objects_bucket.into(),
+                true,
+            );
+        }
+
+        // Owner zero means the object doesn't belong to any Radixian
+        fn check_new_owner(
+            &self,
+            owner: u64,
+        ) {
+            if owner != 0 {
+                assert!(
+                    self.people_resource_manager.non_fungible_exists(&NonFungibleLocalId::integer(owner.into())),
+                    "Owner not found",
+                );
+                assert!(
+                    self.retired_people.get(&owner).is_none(),
+                    "Owner retired",
+                );
+            }
+        }
+
+        fn check_capacity(
+            category: &ObjectCategory,
+            capacity: u32,
+        ) {
+            match category {
+                ObjectCategory::House => assert!(
+                    capacity > 0,
+                    "A house must have room for at least one resident",
+                ),
+                _ => assert!(
+                    capacity == 0,
+                    "Only a house can have residents",
+                ),
+            }
+        }
+
+        // System objects share the id sequence with the object NFTs so that an id always identifies a
+        // single object
+        pub fn add_system_object(
+            &mut self,
+            name: String,
+            daily_rent_price: u32,
+            capacity: u32,
+        ) -> u64 {
+            let object_type = self.object_types.get(&name).expect("Object not found");
+            assert!(
+                object_type.can_be_rent,
+                "This object can't be rent",
+            );
+            assert!(
+                !object_type.retired,
+                "Object type retired",
+            );
+            Self::check_capacity(&object_type.category, capacity);
+            drop(object_type);
+
+            self.last_object_id += 1;
+            self.system_objects.insert(
+                self.last_object_id,
+                SystemObjectData {
+                    name: name.clone(),
+                    daily_rent_price: daily_rent_price,
+                    capacity: capacity,
+                    rent_to: 0,
+                }
+            );
+
+            Runtime::emit_event(
+                SystemObjectEvent {
+                    object_id: self.last_object_id,
+                    name: name,
+                    daily_rent_price: daily_rent_price,
+                    capacity: capacity,
+                    removed: false,
+                }
+            );
+
+            self.last_object_id
+        }
+
+        // The new price only applies to rents started or extended from now on
+        pub fn update_system_object(
+            &mut self,
+            object_id: u64,
+            daily_rent_price: u32,
+            capacity: u32,
+        ) {
+            let residents = self.get_residents(object_id).len() as u32;
+            let mut system_object = self.system_objects.get_mut(&object_id).expect("Object not found");
+            Self::check_capacity(
+                &self.object_types.get(&system_object.name).expect("Object not found").category,
+                capacity,
+            );
+            assert!(
+                capacity >= residents,
+                "Too many residents for this capacity",
+            );
+            system_object.daily_rent_price = daily_rent_price;
+            system_object.capacity = capacity;
+
+            Runtime::emit_event(
+                SystemObjectEvent {
+                    object_id: object_id,
+                    name: system_object.name.clone(),
+                    daily_rent_price: daily_rent_price,
+                    capacity: capacity,
+                    removed: false,
+                }
+            );
+        }
+
+        pub fn remove_system_object(
+            &self,
+            object_id: u64,
+        ) {
+            let system_object = self.system_objects.remove(&object_id).expect("Object not found");
+            assert!(
+                system_object.rent_to == 0,
+                "Object rent",
+            );
+
+            Runtime::emit_event(
+                SystemObjectEvent {
+                    object_id: object_id,
+                    name: system_object.name,
+                    daily_rent_price: system_object.daily_rent_price,
+                    capacity: system_object.capacity,
+                    removed: true,
+                }
+            );
+        }
+
+        pub fn get_system_object(
+            &self,
+            object_id: u64,
+        ) -> Option<SystemObjectData> {
+            self.system_objects.get(&object_id).map(|system_object| system_object.deref().clone())
+        }
+
+        // Every object minted, whatever the method, goes through here so that supply caps and editions
+        // are always respected
+        fn mint_objects(
+            &mut self,
+            name: String,
+            amount: u32,
+            owner: u64,
+        ) -> NonFungibleBucket {
+            let mut object_type = self.object_types.get_mut(&name).expect("Object not found");
+
+            assert!(
+                !object_type.retired,
+                "Object type retired",
+            );
+            if let Some(max_supply) = object_type.max_supply {
+                assert!(
+                    object_type.minted + amount <= max_supply,
+                    "Sold out",
+                );
+            }
+            let first_edition = object_type.minted + 1;
+            object_type.minted += amount;
+            let max_supply = object_type.max_supply;
+            let key_image_url = object_type.key_image_url.clone();
+            drop(object_type);
+
+            let now = Clock::current_time_rounded_to_seconds();
+            let mut ids: Vec<u64> = Vec::new();
+            let mut objects_bucket = NonFungibleBucket::new(self.object_resource_manager.address());
+            for edition in first_edition..first_edition + amount {
+                self.last_object_id += 1;
+
+                objects_bucket.put(
+                    self.object_resource_manager.mint_non_fungible(
+                        &NonFungibleLocalId::integer(self.last_object_id.into()),
+                        ObjectData {
+                            name: name.clone(),
+                            edition: edition,
+                            max_supply: max_supply,
+                            rent_allowed: false,
+                            daily_rent_price: 0,
+                            rent_to: 0,
+                            key_image_url: key_image_url.clone(),
+                            condition: 100,
+                            condition_updated: now,
+                        }
+                    )
+                );
+
+                self.set_object_owner(self.last_object_id, owner);
+
+                ids.push(self.last_object_id);
+            }
+
+            Runtime::emit_event(
+                NewObjectEvent {
+                    name: name,
+                    ids: ids,
+                    first_edition: first_edition,
+                    owner: owner,
+                }
+            );
+
+            objects_bucket
+        }
+
+        pub fn buy_egg(
+            &mut self,
+            mut xrd_bucket: Bucket,
+        ) -> (
+            NonFungibleBucket,
+            Bucket,
+        ) {
+            assert!(
+                xrd_bucket.resource_address() == XRD,
+                "Wrong coin",
+            );
+            assert!(
+                xrd_bucket.amount() >= self.egg_xrd_price,
+                "Insufficient amount",
+            );
+            self.xrd_vault.put(xrd_bucket.take(self.egg_xrd_price));
+
+            assert!(
+                self.eggs_on_sale > 0,
+                "Sale ended",
+            );
+            self.eggs_on_sale -= 1;
+
+            (
+                self.mint_egg(0, 0),
+                xrd_bucket,
             )
         }
 
@@ -552,163 +1828,688 @@ mod radix_life {
             name: String,
             amount: u8,
             mortgaged: bool,
-            owner: u64,
+            owner_proof: Option<Proof>,
         ) -> (
             NonFungibleBucket,
             Bucket,
+        ) {
+            assert!(
+                amount > 0,
+                "Can't buy zero objects",
+            );
+
+            let owner = self.get_owner_id(owner_proof);
+
+            let object_type = self.object_types.get(&name).expect("Object not found");
+
+            assert!(
+                object_type.can_be_bought,
+                "This object can't be bought"
+            );
+
+            let price = object_type.price;
+            if mortgaged {
+                assert!(
+                    object_type.can_be_mortgaged,
+                    "This object can't be mortgaged",
+                );
+            }
+            drop(object_type);
+
+            let objects_bucket = self.mint_objects(name, amount as u32, owner);
+
+            if !mortgaged {
+                self.take_payment(&mut coin_bucket, price * amount as u32, CoinSource::Object);
+
+                return (objects_bucket, coin_bucket);
+            }
+
+            // Each mortgaged object is the collateral of a loan paying part of its price, the owner
+            // pays the rest and gets the object back when the loan is repaid
+            assert!(
+                owner != 0,
+                "A mortgage needs an owner",
+            );
+            let financed = ((price as u64 * self.loan_to_value as u64) / 100) as u32;
+            assert!(
+                financed > 0,
+                "Invalid amount",
+            );
+            for object_id in objects_bucket.non_fungible_local_ids() {
+                let loan_bucket = self.open_loan(
+                    owner,
+                    LoanCollateral::Object(RadixLife::get_u64_id(&object_id)),
+                    financed,
+                );
+                self.burn_coins(loan_bucket.into(), CoinSource::Object);
+            }
+            self.take_payment(&mut coin_bucket, (price - financed) * amount as u32, CoinSource::Object);
+
+            self.collateral_vault.put(objects_bucket);
+
+            (
+                NonFungibleBucket::new(self.object_resource_manager.address()),
+                coin_bucket,
+            )
+        }
+
+        pub fn buy_coins(
+            &mut self,
+            xrd_bucket: Bucket,
+        ) -> FungibleBucket {
+            assert!(
+                xrd_bucket.resource_address() == XRD,
+                "Wrong coin",
+            );
+
+            let coin_amount = xrd_bucket.amount() / self.get_coin_xrd_price();
+
+            self.xrd_vault.put(xrd_bucket);
+
+            self.mint_coins(coin_amount, CoinSource::Xrd)
+        }
+
+        // Returns the trimmed name
+        fn check_name(
+            name: &str,
+        ) -> String {
+            let name = name.trim().to_string();
+            assert!(
+                name.len() > 0 && name.len() < 256,
+                "Invalid name size",
+            );
+            assert!(
+                name.chars().all(|c| c.is_ascii_alphanumeric() || c == ' '),
+                "Illegal character in name"
+            );
+
+            name
+        }
+
+        pub fn give_name(
+            &self,
+            people_proof: Proof,
+            mut name: String,
+        ) {
+            let non_fungible = people_proof.check_with_message(
+                self.people_resource_manager.address(),
+                "Wrong NFT",
+            )
+            .as_non_fungible()
+            .non_fungible::<PeopleData>();
+
+            assert!(
+                non_fungible.data().name.as_str() == "",
+                "Name already assigned",
+            );
+
+            name = RadixLife::check_name(&name);
+
+            self.people_resource_manager.update_non_fungible_data(
+                &non_fungible.local_id(),
+                "name",
+                name.clone(),
+            );
+
+            let local_id = match &non_fungible.local_id() {
+                NonFungibleLocalId::Integer(local_id) => local_id.value(),
+                _ => Runtime::panic("Should not happen".to_string()),
+            };
+
+            Runtime::emit_event(
+                NameEvent {
+                    people_id: local_id,
+                    name: name,
+                }
+            );
+        }
+
+        pub fn deposit_to_bank_account(
+            &self,
+            people_id: u64,
+            coin_bucket: Bucket,
         ) {
             assert!(
                 coin_bucket.resource_address() == self.coin_resource_manager.address(),
                 "Wrong coin",
             );
             assert!(
-                amount > 0,
-                "Can't buy zero objects",
+                self.people_resource_manager.non_fungible_exists(&NonFungibleLocalId::integer(people_id.into())),
+                "Radixian not found",
+            );
+            assert!(
+                self.retired_people.get(&people_id).is_none(),
+                "Radixian retired",
+            );
+            let amount = u32::try_from(coin_bucket.amount().checked_floor().unwrap()).unwrap();
+            assert!(
+                amount > 0,
+                "Invalid amount",
+            );
+
+            self.credit_bank_account(
+                people_id,
+                amount,
+            );
+
+            self.burn_coins(coin_bucket, CoinSource::Bank);
+        }
+
+        pub fn withdraw_from_bank_account(
+            &self,
+            people_proof: Proof,
+            amount: u32,
+        ) -> FungibleBucket {
+            let non_fungible = people_proof.check_with_message(
+                self.people_resource_manager.address(),
+                "Wrong NFT",
+            )
+            .as_non_fungible()
+            .non_fungible::<PeopleData>();
+            let people_id = match non_fungible.local_id() {
+                NonFungibleLocalId::Integer(id) => id.value(),
+                _ => Runtime::panic("Should not happen".to_string()),
+            };
+            assert!(
+                self.retired_people.get(&people_id).is_none(),
+                "Radixian retired",
+            );
+
+            self.accrue_interest(people_id);
+            let balance = self.get_bank_balance(people_id);
+            assert!(
+                balance >= amount,
+                "Insufficient balance",
+            );
+            self.bank_accounts.insert(people_id, balance - amount);
+
+            Runtime::emit_event(
+                BankWithdrawEvent {
+                    amount: amount,
+                    people_id: people_id,
+                }
+            );
+
+            self.mint_coins(Decimal::from(amount), CoinSource::Bank)
+        }
+
+        pub fn bank_transfer(
+            &self,
+            from_proof: Proof,
+            to_people_id: u64,
+            amount: u32,
+        ) {
+            let non_fungible = from_proof.check_with_message(
+                self.people_resource_manager.address(),
+                "Wrong NFT",
+            )
+            .as_non_fungible()
+            .non_fungible::<PeopleData>();
+            let from_people_id = RadixLife::get_u64_id(non_fungible.local_id());
+
+            assert!(
+                self.people_resource_manager.non_fungible_exists(&NonFungibleLocalId::integer(to_people_id.into())),
+                "Radixian not found",
+            );
+            assert!(
+                self.retired_people.get(&to_people_id).is_none(),
+                "Radixian retired",
+            );
+            assert!(
+                from_people_id != to_people_id,
+                "Can't transfer to yourself",
+            );
+            assert!(
+                amount > 0,
+                "Invalid amount",
+            );
+
+            self.accrue_interest(from_people_id);
+            let balance = self.get_bank_balance(from_people_id);
+            assert!(
+                balance >= amount,
+                "Insufficient balance",
+            );
+            self.bank_accounts.insert(from_people_id, balance - amount);
+
+            self.accrue_interest(to_people_id);
+            let balance = self.get_bank_balance(to_people_id);
+            self.bank_accounts.insert(to_people_id, balance + amount);
+
+            Runtime::emit_event(
+                BankTransferEvent {
+                    from_people_id: from_people_id,
+                    to_people_id: to_people_id,
+                    amount: amount,
+                }
+            );
+        }
+
+        pub fn get_bank_balance(
+            &self,
+            people_id: u64,
+        ) -> u32 {
+            match self.bank_accounts.get(&people_id) {
+                None => 0,
+                Some(balance) => *balance,
+            }
+        }
+
+        // Annual percentage rate as a fraction, 0.05 means 5%
+        pub fn update_savings_apr(
+            &mut self,
+            savings_apr: Decimal,
+        ) {
+            assert!(
+                savings_apr >= Decimal::ZERO,
+                "Invalid APR",
+            );
+            self.savings_apr = savings_apr;
+        }
+
+        // Interest is accrued lazily, every time the balance of the account changes, on the balance held
+        // since the previous time
+        fn accrue_interest(
+            &self,
+            people_id: u64,
+        ) {
+            let now = Clock::current_time_rounded_to_seconds();
+            let accrued_at = self.interest_accrued_at.get(&people_id).map(|accrued_at| *accrued_at);
+            self.interest_accrued_at.insert(people_id, now);
+
+            let elapsed = match accrued_at {
+                None => return,
+                Some(accrued_at) => now.seconds_since_unix_epoch - accrued_at.seconds_since_unix_epoch,
+            };
+            let balance = self.get_bank_balance(people_id);
+            if elapsed <= 0 || balance == 0 || self.savings_apr == Decimal::ZERO {
+                return;
+            }
+
+            // Only whole coins are credited, the rest is kept for the next accrual
+            let accrued = Decimal::from(balance) * self.savings_apr * Decimal::from(elapsed) / Decimal::from(SECONDS_PER_YEAR)
+                + self.interest_remainders.get(&people_id).map(|remainder| *remainder).unwrap_or(Decimal::ZERO);
+            let interest = accrued.checked_floor().unwrap();
+            self.interest_remainders.insert(people_id, accrued - interest);
+            let interest = u32::try_from(interest).unwrap();
+            if interest == 0 {
+                return;
+            }
+            self.bank_accounts.insert(people_id, balance + interest);
+
+            Runtime::emit_event(
+                InterestAccruedEvent {
+                    people_id: people_id,
+                    amount: interest,
+                    balance: balance + interest,
+                }
+            );
+        }
+
+        // The interest on the previous balance is accrued first, the credited amount only earns from now
+        fn credit_bank_account(
+            &self,
+            people_id: u64,
+            amount: u32,
+        ) {
+            self.accrue_interest(people_id);
+            let balance = self.get_bank_balance(people_id);
+            self.bank_accounts.insert(people_id, balance + amount);
+
+            Runtime::emit_event(
+                BankDepositEvent {
+                    people_id: people_id,
+                    amount: amount,
+                }
+            );
+        }
+
+        pub fn set_people_field_domain(
+            &mut self,
+            field: String,
+            values: Option<Vec<String>>,
+        ) {
+            assert!(
+                PEOPLE_STRING_FIELDS.contains(&field.as_str()),
+                "Unknown field",
             );
 
-            let object_type = self.object_types.get(&name).expect("Object not found");
+            match values {
+                None => {
+                    self.people_field_domains.remove(&field);
+                },
+                Some(values) => {
+                    assert!(
+                        !values.is_empty(),
+                        "Empty domain",
+                    );
+                    self.people_field_domains.insert(field, values);
+                },
+            }
+        }
 
-            assert!(
-                object_type.can_be_bought,
-                "This object can't be bought"
-            );
+        fn get_people_string_field(
+            people_data: &PeopleData,
+            field: &str,
+        ) -> String {
+            match field {
+                "name" => people_data.name.clone(),
+                "gender" => people_data.gender.as_str().to_string(),
+                "occupation" => people_data.occupation.clone(),
+                "schooling" => people_data.schooling.as_str().to_string(),
+                _ => Runtime::panic(format!("Field {} can't be updated", field)),
+            }
+        }
 
-            let total_price = match mortgaged {
-                false => object_type.price * amount as u32,
-                true => {
+        // Typed fields are received as their display strings and parsed here, so the backend can keep
+        // sending the same values it used with the untyped model
+        fn set_people_string_field(
+            &self,
+            id: &NonFungibleLocalId,
+            field: &str,
+            value: &str,
+        ) {
+            match field {
+                "gender" => self.people_resource_manager.update_non_fungible_data(
+                    id,
+                    field,
+                    Gender::from_str(value).unwrap_or_else(|error| Runtime::panic(error)),
+                ),
+                "schooling" => self.people_resource_manager.update_non_fungible_data(
+                    id,
+                    field,
+                    Schooling::from_str(value).unwrap_or_else(|error| Runtime::panic(error)),
+                ),
+                _ => self.people_resource_manager.update_non_fungible_data(
+                    id,
+                    field,
+                    value.to_string(),
+                ),
+            }
+        }
+
+        pub fn update_people_data(
+            &self,
+            people_id: u64,
+            non_fungible_data: Option<HashMap<String, String>>,
+            partner: Option<u64>,
+            key_image_url: Option<String>,
+        ) {
+            let id = NonFungibleLocalId::integer(people_id.into());
+            let people_data = self.people_resource_manager.get_non_fungible_data::<PeopleData>(&id);
+
+            let mut changes: Vec<PeopleFieldChange> = Vec::new();
+
+            match non_fungible_data {
+                None => {},
+                Some(non_fungible_data) => {
+                    let mut fields: Vec<&String> = non_fungible_data.keys().collect();
+                    fields.sort();
+
+                    for field in fields {
+                        let value = match field.as_str() {
+                            "name" => RadixLife::check_name(non_fungible_data.get(field).unwrap()),
+                            _ => non_fungible_data.get(field).unwrap().clone(),
+                        };
+                        let old_value = RadixLife::get_people_string_field(&people_data, field);
+
+                        if let Some(domain) = self.people_field_domains.get(field) {
+                            assert!(
+                                domain.contains(&value),
+                                "Value not allowed for {}",
+                                field,
+                            );
+                        }
+
+                        self.set_people_string_field(&id, field, &value);
+
+                        changes.push(
+                            PeopleFieldChange {
+                                field: field.clone(),
+                                old_value: old_value,
+                                new_value: value,
+                            }
+                        );
+                    }
+                }
+            }
+
+            match partner {
+                None => {},
+                Some(partner) => {
                     assert!(
-                        object_type.can_be_mortgaged,
-                        "This object can't be mortgaged",
+                        partner == 0 || (
+                            partner != people_id &&
+                            self.people_resource_manager.non_fungible_exists(&NonFungibleLocalId::integer(partner.into()))
+                        ),
+                        "Invalid partner",
+                    );
+
+                    self.people_resource_manager.update_non_fungible_data(
+                        &id,
+                        "partner",
+                        partner
+                    );
+
+                    changes.push(
+                        PeopleFieldChange {
+                            field: "partner".to_string(),
+                            old_value: people_data.partner.to_string(),
+                            new_value: partner.to_string(),
+                        }
                     );
-                    object_type.price * amount as u32 / 2
                 },
-            };
-            coin_bucket.take(Decimal::from(total_price)).burn();
+            }
 
-            let mut ids: Vec<u64> = Vec::new();
+            match key_image_url {
+                None => {},
+                Some(key_image_url) => {
+                    self.people_resource_manager.update_non_fungible_data(
+                        &id,
+                        "key_image_url",
+                        UncheckedUrl(key_image_url.clone())
+                    );
 
-            let first = self.last_object_id + 1;
-            self.last_object_id += amount as u64;
-            let mut objects_bucket = NonFungibleBucket::new(self.object_resource_manager.address());
-            for id in first..=self.last_object_id {
-                objects_bucket.put(
-                    self.object_resource_manager.mint_non_fungible(
-                        &NonFungibleLocalId::integer(id.into()),
-                        ObjectData {
-                            name: name.clone(),
-                            mortgaged: mortgaged,
-                            rent_allowed: false,
-                            daily_rent_price: 0,
-                            rent_to: 0,
-                            key_image_url: object_type.key_image_url.clone(),
+                    changes.push(
+                        PeopleFieldChange {
+                            field: "key_image_url".to_string(),
+                            old_value: people_data.key_image_url.0.clone(),
+                            new_value: key_image_url,
                         }
-                    )
+                    );
+                },
+            }
+
+            if !changes.is_empty() {
+                Runtime::emit_event(
+                    PeopleUpdatedEvent {
+                        people_id: people_id,
+                        changes: changes,
+                    }
                 );
+            }
+        }
+
+        pub fn update_object_data(
+            &self,
+            object_id: u64,
+            rent_to: Option<u64>,
+        ) {
+            match rent_to {
+                None => {},
+                Some(rent_to) => {
+                    let nf_object_id = NonFungibleLocalId::integer(object_id.into());
+                    self.update_object_condition(
+                        &nf_object_id,
+                        &self.object_resource_manager.get_non_fungible_data::<ObjectData>(&nf_object_id),
+                    );
 
-                ids.push(id);
+                    self.object_resource_manager.update_non_fungible_data(
+                        &NonFungibleLocalId::integer(object_id.into()),
+                        "rent_to",
+                        rent_to
+                    );
+                },
             }
+        }
 
-            Runtime::emit_event(
-                NewObjectEvent {
-                    name: name,
-                    ids: ids,
-                    mortgaged: mortgaged,
-                    owner: owner,
-                }
+        pub fn update_loan_terms(
+            &mut self,
+            loan_interest: u8,
+            loan_term: i64,
+            loan_to_value: u8,
+        ) {
+            assert!(
+                loan_term > 0,
+                "Term must be bigger than zero",
+            );
+            assert!(
+                loan_to_value > 0 && loan_to_value <= 100,
+                "Invalid loan to value",
             );
 
-            (
-                objects_bucket,
-                coin_bucket,
-            )
+            self.loan_interest = loan_interest;
+            self.loan_term = loan_term;
+            self.loan_to_value = loan_to_value;
         }
 
-        pub fn buy_coins(
+        // Borrow against the bank balance: the collateral needed according to the loan to value ratio
+        // is locked until the loan is repaid
+        pub fn borrow(
             &mut self,
-            xrd_bucket: Bucket,
+            people_proof: Proof,
+            amount: u32,
         ) -> FungibleBucket {
+            let non_fungible = people_proof.check_with_message(
+                self.people_resource_manager.address(),
+                "Wrong NFT",
+            )
+            .as_non_fungible()
+            .non_fungible::<PeopleData>();
+            let people_id = RadixLife::get_u64_id(non_fungible.local_id());
+
             assert!(
-                xrd_bucket.resource_address() == XRD,
-                "Wrong coin",
+                amount > 0,
+                "Invalid amount",
             );
+            let collateral = ((amount as u64 * 100).div_ceil(self.loan_to_value as u64)) as u32;
 
-            let coin_amount = xrd_bucket.amount() / self.coin_xrd_price;
-
-            self.xrd_vault.put(xrd_bucket);
+            self.accrue_interest(people_id);
+            let balance = self.get_bank_balance(people_id);
+            assert!(
+                balance >= collateral,
+                "Insufficient balance",
+            );
+            self.bank_accounts.insert(people_id, balance - collateral);
 
-            self.coin_resource_manager.mint(coin_amount)
+            self.open_loan(people_id, LoanCollateral::BankBalance(collateral), amount)
         }
 
-        pub fn give_name(
-            &self,
+        // Borrow against an object, which is kept in the collateral vault until the loan is repaid
+        pub fn borrow_with_object(
+            &mut self,
             people_proof: Proof,
-            mut name: String,
-        ) {
+            object_bucket: NonFungibleBucket,
+        ) -> FungibleBucket {
             let non_fungible = people_proof.check_with_message(
                 self.people_resource_manager.address(),
                 "Wrong NFT",
             )
             .as_non_fungible()
             .non_fungible::<PeopleData>();
+            let people_id = RadixLife::get_u64_id(non_fungible.local_id());
 
             assert!(
-                non_fungible.data().name.as_str() == "",
-                "Name already assigned",
+                object_bucket.resource_address() == self.object_resource_manager.address(),
+                "Wrong NFT",
             );
+            let object = object_bucket.non_fungible::<ObjectData>();
+            let object_id = RadixLife::get_u64_id(object.local_id());
+            let object_data = object.data();
 
-            name = name.trim().to_string();
+            let price = {
+                let object_type = self.object_types.get(&object_data.name).expect("Object not found");
+                assert!(
+                    object_type.can_be_mortgaged,
+                    "This object can't be mortgaged",
+                );
+                object_type.price
+            };
             assert!(
-                name.len() > 0 && name.len() < 256,
-                "Invalid name size",
+                !object_data.rent_allowed && object_data.rent_to == 0,
+                "Can't mortgage rented object",
             );
             assert!(
-                name.chars().all(|c| c.is_ascii_alphanumeric() || c == ' '),
-                "Illegal character in name"
+                self.residents.get(&object_id).is_none(),
+                "Can't mortgage an inhabited house",
             );
-            
-            self.people_resource_manager.update_non_fungible_data(
-                &non_fungible.local_id(),
-                "name",
-                name.clone(),
+            assert!(
+                self.update_object_condition(object.local_id(), &object_data) > 0,
+                "Broken object",
             );
 
-            let local_id = match &non_fungible.local_id() {
-                NonFungibleLocalId::Integer(local_id) => local_id.value(),
-                _ => Runtime::panic("Should not happen".to_string()),
-            };
+            self.collateral_vault.put(object_bucket);
 
-            Runtime::emit_event(
-                NameEvent {
-                    people_id: local_id,
-                    name: name,
-                }
+            let amount = ((price as u64 * self.loan_to_value as u64) / 100) as u32;
+            assert!(
+                amount > 0,
+                "Invalid amount",
             );
+
+            self.open_loan(people_id, LoanCollateral::Object(object_id), amount)
         }
 
-        pub fn deposit_to_bank_account(
-            &self,
+        fn open_loan(
+            &mut self,
             people_id: u64,
-            coin_bucket: Bucket,
-        ) {
+            collateral: LoanCollateral,
+            amount: u32,
+        ) -> FungibleBucket {
             assert!(
-                coin_bucket.resource_address() == self.coin_resource_manager.address(),
-                "Wrong coin",
+                self.retired_people.get(&people_id).is_none(),
+                "Radixian retired",
+            );
+
+            let interest = ((amount as u64 * self.loan_interest as u64).div_ceil(100)) as u32;
+            let due_date = Clock::current_time_rounded_to_seconds().add_seconds(self.loan_term).unwrap();
+
+            self.last_loan_id += 1;
+            self.loans.insert(
+                self.last_loan_id,
+                LoanData {
+                    people_id: people_id,
+                    collateral: collateral,
+                    amount: amount,
+                    outstanding: amount + interest,
+                    due_date: due_date,
+                }
             );
+            RadixLife::add_to_list(&self.people_loans, people_id, self.last_loan_id);
 
             Runtime::emit_event(
-                BankDepositEvent {
+                LoanEvent {
+                    loan_id: self.last_loan_id,
                     people_id: people_id,
-                    amount: u32::try_from(coin_bucket.amount().checked_floor().unwrap()).unwrap(),
+                    collateral: collateral,
+                    amount: amount,
+                    outstanding: amount + interest,
+                    due_date: due_date.seconds_since_unix_epoch,
                 }
             );
 
-            coin_bucket.burn();
+            self.mint_coins(Decimal::from(amount), CoinSource::Loan)
         }
 
-        pub fn withdraw_from_bank_account(
-            &self,
+        // Loans can be repaid in more than one installment, the collateral is released with the last one
+        pub fn repay(
+            &mut self,
             people_proof: Proof,
+            loan_id: u64,
             amount: u32,
+            mut coin_bucket: Bucket,
+        ) -> (
+            Option<NonFungibleBucket>,
+            Bucket,
         ) {
             let non_fungible = people_proof.check_with_message(
                 self.people_resource_manager.address(),
@@ -716,98 +2517,251 @@ mod radix_life {
             )
             .as_non_fungible()
             .non_fungible::<PeopleData>();
-            let people_id = match non_fungible.local_id() {
-                NonFungibleLocalId::Integer(id) => id.value(),
-                _ => Runtime::panic("Should not happen".to_string()),
-            };
+            let people_id = RadixLife::get_u64_id(non_fungible.local_id());
+
+            let mut loan = self.loans.get_mut(&loan_id).expect("Loan not found");
+            assert!(
+                loan.people_id == people_id,
+                "Not your loan",
+            );
+            let amount = amount.min(loan.outstanding);
+            loan.outstanding -= amount;
+            let outstanding = loan.outstanding;
+            let collateral = loan.collateral;
+            drop(loan);
+
+            self.take_payment(&mut coin_bucket, amount, CoinSource::Loan);
 
             Runtime::emit_event(
-                BankWithdrawEvent {
+                LoanRepaidEvent {
+                    loan_id: loan_id,
                     amount: amount,
-                    people_id: people_id,
+                    outstanding: outstanding,
                 }
             );
+
+            if outstanding > 0 {
+                return (None, coin_bucket);
+            }
+
+            self.loans.remove(&loan_id);
+            RadixLife::remove_from_list(&self.people_loans, people_id, loan_id);
+
+            match collateral {
+                LoanCollateral::BankBalance(collateral) => {
+                    self.credit_bank_account(people_id, collateral);
+                    (None, coin_bucket)
+                },
+                LoanCollateral::Object(object_id) => (
+                    Some(self.collateral_vault.take_non_fungible(&NonFungibleLocalId::integer(object_id.into()))),
+                    coin_bucket,
+                ),
+            }
         }
 
-        pub fn update_people_data(
-            &self,
-            people_id: u64,
-            non_fungible_data: Option<HashMap<String, String>>,
-            partner: Option<u64>,
-            key_image_url: Option<String>,
+        // Anyone can liquidate an expired loan: a locked bank balance pays the outstanding amount and
+        // the rest goes back to the borrower, an object is burned
+        pub fn liquidate(
+            &mut self,
+            loan_id: u64,
         ) {
-            let id = NonFungibleLocalId::integer(people_id.into());
+            let loan = self.loans.remove(&loan_id).expect("Loan not found");
+            assert!(
+                Clock::current_time_is_strictly_after(loan.due_date, TimePrecision::Second),
+                "Loan not expired",
+            );
 
-            match non_fungible_data {
-                None => {},
-                Some(non_fungible_data) => {
-                    for (name, value) in non_fungible_data.iter() {
-                        self.people_resource_manager.update_non_fungible_data(
-                            &id,
-                            name,
-                            value
-                        );
+            RadixLife::remove_from_list(&self.people_loans, loan.people_id, loan_id);
+
+            self.seize_collateral(loan_id, loan);
+        }
+
+        // A retired borrower with no heir gets nothing back
+        fn seize_collateral(
+            &mut self,
+            loan_id: u64,
+            loan: LoanData,
+        ) {
+            match loan.collateral {
+                LoanCollateral::BankBalance(collateral) => {
+                    if collateral > loan.outstanding && self.retired_people.get(&loan.people_id).is_none() {
+                        self.credit_bank_account(loan.people_id, collateral - loan.outstanding);
                     }
+                },
+                LoanCollateral::Object(object_id) => {
+                    self.set_object_owner(object_id, 0);
+                    self.collateral_vault.take_non_fungible(&NonFungibleLocalId::integer(object_id.into())).burn();
+                },
+            }
+
+            Runtime::emit_event(
+                LoanLiquidatedEvent {
+                    loan_id: loan_id,
+                    people_id: loan.people_id,
+                    collateral: loan.collateral,
+                    outstanding: loan.outstanding,
                 }
+            );
+        }
+
+        pub fn get_loan(
+            &self,
+            loan_id: u64,
+        ) -> Option<LoanData> {
+            self.loans.get(&loan_id).map(|loan| loan.deref().clone())
+        }
+
+        pub fn get_loans(
+            &self,
+            people_id: u64,
+        ) -> Vec<u64> {
+            match self.people_loans.get(&people_id) {
+                None => vec![],
+                Some(loans) => loans.deref().clone(),
             }
+        }
 
-            match partner {
-                None => {},
-                Some(partner) => {
-                    self.people_resource_manager.update_non_fungible_data(
-                        &id,
-                        "partner",
-                        partner
-                    );
+        fn get_u64_id(
+            local_id: &NonFungibleLocalId,
+        ) -> u64 {
+            match local_id {
+                NonFungibleLocalId::Integer(id) => id.value(),
+                _ => Runtime::panic("Should not happen".to_string()),
+            }
+        }
+
+        fn add_to_list(
+            list_store: &KeyValueStore<u64, Vec<u64>>,
+            key: u64,
+            value: u64,
+        ) {
+            let mut list = match list_store.get(&key) {
+                None => vec![],
+                Some(list) => list.deref().clone(),
+            };
+            list.push(value);
+            list_store.insert(key, list);
+        }
+
+        fn remove_from_list(
+            list_store: &KeyValueStore<u64, Vec<u64>>,
+            key: u64,
+            value: u64,
+        ) {
+            let mut list = match list_store.get(&key) {
+                None => return,
+                Some(list) => list.deref().clone(),
+            };
+            list.retain(|item| *item != value);
+            match list.is_empty() {
+                true => {
+                    list_store.remove(&key);
                 },
+                false => list_store.insert(key, list),
             }
+        }
 
-            match key_image_url {
-                None => {},
-                Some(key_image_url) => {
-                    self.people_resource_manager.update_non_fungible_data(
-                        &id,
-                        "key_image_url",
-                        UncheckedUrl(key_image_url)
+        // No proof means the objects don't belong to any Radixian
+        fn get_owner_id(
+            &self,
+            owner_proof: Option<Proof>,
+        ) -> u64 {
+            match owner_proof {
+                None => 0,
+                Some(owner_proof) => {
+                    let non_fungible = owner_proof.check_with_message(
+                        self.people_resource_manager.address(),
+                        "Wrong NFT",
+                    )
+                    .as_non_fungible()
+                    .non_fungible::<PeopleData>();
+                    let owner = RadixLife::get_u64_id(non_fungible.local_id());
+
+                    assert!(
+                        self.retired_people.get(&owner).is_none(),
+                        "Owner retired",
                     );
+
+                    owner
                 },
             }
         }
 
-        pub fn update_object_data(
+        // Owner zero means the object doesn't belong to any Radixian
+        fn set_object_owner(
             &self,
             object_id: u64,
-            mortgaged: Option<bool>,
-            rent_to: Option<u64>,
+            owner: u64,
         ) {
-            match mortgaged {
-                None => {},
-                Some(mortgaged) => {
-                    self.object_resource_manager.update_non_fungible_data(
-                        &NonFungibleLocalId::integer(object_id.into()),
-                        "mortgaged",
-                        mortgaged
-                    );
-                },
+            let previous_owner = match self.object_owners.get(&object_id) {
+                None => 0,
+                Some(previous_owner) => *previous_owner,
+            };
+            if previous_owner != 0 {
+                RadixLife::remove_from_list(&self.owned_objects, previous_owner, object_id);
             }
 
-            match rent_to {
-                None => {},
-                Some(rent_to) => {
-                    self.object_resource_manager.update_non_fungible_data(
-                        &NonFungibleLocalId::integer(object_id.into()),
-                        "rent_to",
-                        rent_to
-                    );
+            match owner {
+                0 => {
+                    self.object_owners.remove(&object_id);
+                },
+                _ => {
+                    self.object_owners.insert(object_id, owner);
+                    RadixLife::add_to_list(&self.owned_objects, owner, object_id);
                 },
             }
         }
 
-        pub fn mortgage(
+        // Objects lose daily_decay condition points per day, twice as much while rented.
+        // Only whole days are accounted so that frequent updates don't stop the decay
+        fn update_object_condition(
             &self,
+            nf_object_id: &NonFungibleLocalId,
+            non_fungible_data: &ObjectData,
+        ) -> u8 {
+            let daily_decay = self.object_types.get(&non_fungible_data.name).expect("Object not found").daily_decay;
+            let now = Clock::current_time_rounded_to_seconds();
+            let days = (now.seconds_since_unix_epoch - non_fungible_data.condition_updated.seconds_since_unix_epoch) / 86400;
+            if daily_decay == 0 || days <= 0 {
+                return non_fungible_data.condition;
+            }
+
+            let multiplier = match non_fungible_data.rent_to {
+                0 => 1,
+                _ => 2,
+            };
+            let decay = (days * daily_decay as i64 * multiplier).min(100) as u8;
+            let condition = non_fungible_data.condition.saturating_sub(decay);
+
+            self.object_resource_manager.update_non_fungible_data(
+                nf_object_id,
+                "condition_updated",
+                non_fungible_data.condition_updated.add_days(days).unwrap(),
+            );
+            if condition != non_fungible_data.condition {
+                self.object_resource_manager.update_non_fungible_data(
+                    nf_object_id,
+                    "condition",
+                    condition,
+                );
+
+                Runtime::emit_event(
+                    ObjectConditionEvent {
+                        object_id: RadixLife::get_u64_id(nf_object_id),
+                        condition: condition,
+                    }
+                );
+            }
+
+            condition
+        }
+
+        // The repair cost is the object price multiplied by the missing condition percentage
+        pub fn repair(
+            &mut self,
             object_proof: Proof,
-            deposit_account: Option<u64>,
-        ) -> Option<FungibleBucket> {
+            mut coin_bucket: Bucket,
+        ) -> Bucket {
             let non_fungible = object_proof.check_with_message(
                 self.object_resource_manager.address(),
                 "Wrong NFT",
@@ -816,46 +2770,35 @@ mod radix_life {
             .non_fungible::<ObjectData>();
             let non_fungible_data = non_fungible.data();
 
-            let object_type = self.object_types.get(&non_fungible_data.name).expect("Object not found");
-
-            assert!(
-                object_type.can_be_mortgaged,
-                "This object can't be mortgaged",
-            );
+            let condition = self.update_object_condition(non_fungible.local_id(), &non_fungible_data);
             assert!(
-                !non_fungible_data.mortgaged,
-                "Object already mortgaged",
+                condition < 100,
+                "Object doesn't need repair",
             );
 
+            let price = self.object_types.get(&non_fungible_data.name).expect("Object not found").price;
+            let cost = (price as u64 * (100 - condition) as u64).div_ceil(100) as u32;
+            self.take_payment(&mut coin_bucket, cost, CoinSource::Repair);
+
             self.object_resource_manager.update_non_fungible_data(
                 non_fungible.local_id(),
-                "mortgaged",
-                true
+                "condition",
+                100u8,
+            );
+            self.object_resource_manager.update_non_fungible_data(
+                non_fungible.local_id(),
+                "condition_updated",
+                Clock::current_time_rounded_to_seconds(),
             );
 
-            let amount = object_type.price / 2;
-
-            match deposit_account {
-                None => Some(self.coin_resource_manager.mint(amount)),
-                Some(people_id) => {
-                    Runtime::emit_event(
-                        BankDepositEvent {
-                            people_id: people_id,
-                            amount: amount,
-                        }
-                    );
-                    None
-                },
-            }
-        }
+            Runtime::emit_event(
+                RepairEvent {
+                    object_id: RadixLife::get_u64_id(non_fungible.local_id()),
+                    cost: cost,
+                }
+            );
 
-        fn get_u64_id(
-            local_id: &NonFungibleLocalId,
-        ) -> u64 {
-            match local_id {
-                NonFungibleLocalId::Integer(id) => id.value(),
-                _ => Runtime::panic("Should not happen".to_string()),
-            }
+            coin_bucket
         }
 
         pub fn allow_rent(
@@ -885,33 +2828,312 @@ mod radix_life {
                 "Can't update price on already rent objects",
             );
 
+            let object_id = RadixLife::get_u64_id(non_fungible.local_id());
+
+            // The rent is credited to the bank account of the given Radixian or, if none, of the object owner
+            let beneficiary = match allow {
+                false => {
+                    self.rent_beneficiaries.remove(&object_id);
+                    None
+                },
+                true => {
+                    let beneficiary = account.unwrap_or(self.get_object_owner(object_id));
+                    assert!(
+                        beneficiary != 0,
+                        "No beneficiary",
+                    );
+                    assert!(
+                        self.people_resource_manager.non_fungible_exists(&NonFungibleLocalId::integer(beneficiary.into())),
+                        "Beneficiary not found",
+                    );
+                    assert!(
+                        self.retired_people.get(&beneficiary).is_none(),
+                        "Beneficiary retired",
+                    );
+                    self.rent_beneficiaries.insert(object_id, beneficiary);
+                    Some(beneficiary)
+                },
+            };
+
             self.object_resource_manager.update_non_fungible_data(
                 non_fungible.local_id(),
                 "rent_allowed",
                 allow
             );
             if allow {
-                self.object_resource_manager.update_non_fungible_data(
-                    non_fungible.local_id(),
-                    "daily_rent_price",
-                    daily_price.unwrap()
-                );
+                if let Some(daily_price) = daily_price {
+                    self.object_resource_manager.update_non_fungible_data(
+                        non_fungible.local_id(),
+                        "daily_rent_price",
+                        daily_price
+                    );
+                }
             }
 
             Runtime::emit_event(
                 AllowRentEvent {
-                    object_id: RadixLife::get_u64_id(non_fungible.local_id()),
+                    object_id: object_id,
                     allow: allow,
                     daily_price: daily_price,
-                    account: account,
+                    account: beneficiary,
+                }
+            );
+        }
+
+        // If the beneficiary has been retired the rent goes to the current owner of the object, who
+        // inherited it
+        fn get_rent_beneficiary(
+            &self,
+            object_id: u64,
+        ) -> u64 {
+            let beneficiary = match self.rent_beneficiaries.get(&object_id) {
+                None => 0,
+                Some(beneficiary) => *beneficiary,
+            };
+
+            match beneficiary != 0 && self.retired_people.get(&beneficiary).is_none() {
+                true => beneficiary,
+                false => self.get_object_owner(object_id),
+            }
+        }
+
+        // Takes the rent for the given number of days from the coin bucket and credits it to the
+        // beneficiary; the paid period is added to the one already paid, if not expired.
+        // The rent of system objects has no beneficiary, the coins are just burned
+        fn collect_rent(
+            &mut self,
+            object_id: u64,
+            people_id: u64,
+            daily_price: u32,
+            days: u32,
+            coin_bucket: Option<&mut Bucket>,
+        ) {
+            let amount = daily_price * days;
+            let now = Clock::current_time_rounded_to_seconds();
+
+            let paid_from = match self.rent_paid_until.get(&object_id) {
+                Some(paid_until) if paid_until.seconds_since_unix_epoch > now.seconds_since_unix_epoch => *paid_until,
+                _ => now,
+            };
+            let paid_until = paid_from.add_days(days as i64).unwrap();
+            self.rent_paid_until.insert(object_id, paid_until);
+
+            let system_object = self.system_objects.get(&object_id).is_some();
+            let beneficiary = match system_object {
+                true => 0,
+                false => self.get_rent_beneficiary(object_id),
+            };
+            if amount > 0 {
+                let coin_bucket = coin_bucket.expect("Missing payment");
+                assert!(
+                    system_object || beneficiary != 0,
+                    "No beneficiary",
+                );
+                self.take_payment(coin_bucket, amount, CoinSource::Rent);
+
+                if beneficiary != 0 {
+                    self.credit_bank_account(beneficiary, amount);
+                }
+            }
+
+            Runtime::emit_event(
+                RentIncomeEvent {
+                    object_id: object_id,
+                    people_id: people_id,
+                    beneficiary: beneficiary,
+                    amount: amount,
+                    paid_until: paid_until.seconds_since_unix_epoch,
+                }
+            );
+        }
+
+        // Who is renting the object and its daily price, both for object NFTs and system objects
+        fn get_rent_terms(
+            &self,
+            object_id: u64,
+        ) -> (u64, u32) {
+            let nf_object_id = NonFungibleLocalId::integer(object_id.into());
+            if self.object_resource_manager.non_fungible_exists(&nf_object_id) {
+                let non_fungible_data = self.object_resource_manager.get_non_fungible_data::<ObjectData>(&nf_object_id);
+                return (non_fungible_data.rent_to, non_fungible_data.daily_rent_price);
+            }
+
+            let system_object = self.system_objects.get(&object_id).expect("Object not found");
+            (system_object.rent_to, system_object.daily_rent_price)
+        }
+
+        pub fn pay_rent(
+            &mut self,
+            people_proof: Proof,
+            object_id: u64,
+            days: u32,
+            mut coin_bucket: Bucket,
+        ) -> Bucket {
+            let non_fungible = people_proof.check_with_message(
+                self.people_resource_manager.address(),
+                "Wrong NFT",
+            )
+            .as_non_fungible()
+            .non_fungible::<PeopleData>();
+            let people_id = RadixLife::get_u64_id(non_fungible.local_id());
+
+            assert!(
+                days > 0,
+                "Invalid number of days",
+            );
+
+            let (rent_to, daily_rent_price) = self.get_rent_terms(object_id);
+            assert!(
+                rent_to == people_id,
+                "Object not rent to you",
+            );
+
+            self.collect_rent(object_id, people_id, daily_rent_price, days, Some(&mut coin_bucket));
+
+            coin_bucket
+        }
+
+        pub fn rent(
+            &mut self,
+            people_proof: Proof,
+            name: String,
+            object_id: u64,
+            days: u32,
+            mut coin_bucket: Option<Bucket>,
+        ) -> Option<Bucket> {
+            let non_fungible = people_proof.check_with_message(
+                self.people_resource_manager.address(),
+                "Wrong NFT",
+            )
+            .as_non_fungible()
+            .non_fungible::<PeopleData>();
+            let people_id = match non_fungible.local_id() {
+                NonFungibleLocalId::Integer(id) => id.value(),
+                _ => Runtime::panic("Should not happen".to_string()),
+            };
+
+            let object_type = self.object_types.get(&name).expect("Object not found");
+            assert!(
+                object_type.can_be_rent,
+                "This object can't be rent",
+            );
+            let house = object_type.category == ObjectCategory::House;
+            drop(object_type);
+
+            assert!(
+                days > 0,
+                "Invalid number of days",
+            );
+
+            let nf_object_id = NonFungibleLocalId::Integer(object_id.into());
+
+            if self.object_resource_manager.non_fungible_exists(&nf_object_id) {
+                let non_fungible_data = self.object_resource_manager.get_non_fungible_data::<ObjectData>(&nf_object_id);
+                assert!(
+                    non_fungible_data.rent_allowed,
+                    "Object not for rent",
+                );
+                assert!(
+                    non_fungible_data.rent_to == 0,
+                    "Object already rent",
+                );
+                assert!(
+                    name == non_fungible_data.name,
+                    "Wrong name"
+                );
+                assert!(
+                    self.update_object_condition(&nf_object_id, &non_fungible_data) > 0,
+                    "Broken object",
+                );
+
+                self.collect_rent(object_id, people_id, non_fungible_data.daily_rent_price, days, coin_bucket.as_mut());
+
+                self.object_resource_manager.update_non_fungible_data(
+                    &nf_object_id,
+                    "rent_to",
+                    people_id,
+                );
+            } else {
+                let mut system_object = self.system_objects.get_mut(&object_id).expect("Object not found");
+                assert!(
+                    system_object.rent_to == 0,
+                    "Object already rent",
+                );
+                assert!(
+                    name == system_object.name,
+                    "Wrong name"
+                );
+                system_object.rent_to = people_id;
+                let daily_rent_price = system_object.daily_rent_price;
+                drop(system_object);
+
+                self.collect_rent(object_id, people_id, daily_rent_price, days, coin_bucket.as_mut());
+            }
+
+            // Whoever rents a house lives in it; the previous residents, if any, were invited by
+            // the owner and have to leave
+            if house {
+                for resident in self.get_residents(object_id) {
+                    self.set_residence(resident, 0);
+                }
+                self.set_residence(people_id, object_id);
+            }
+
+            Runtime::emit_event(
+                RentEvent {
+                    object_id: object_id,
+                    people_id: people_id,
+                }
+            );
+
+            coin_bucket
+        }
+
+        // Object zero means homeless
+        fn set_residence(
+            &self,
+            people_id: u64,
+            object_id: u64,
+        ) {
+            let id = NonFungibleLocalId::integer(people_id.into());
+            let residence = self.people_resource_manager.get_non_fungible_data::<PeopleData>(&id).residence;
+            if residence == object_id {
+                return;
+            }
+
+            if residence != 0 {
+                RadixLife::remove_from_list(&self.residents, residence, people_id);
+            }
+            if object_id != 0 {
+                RadixLife::add_to_list(&self.residents, object_id, people_id);
+            }
+
+            self.people_resource_manager.update_non_fungible_data(&id, "residence", object_id);
+
+            Runtime::emit_event(
+                ResidenceEvent {
+                    people_id: people_id,
+                    object_id: object_id,
                 }
             );
         }
 
-        pub fn rent(
+        pub fn get_residents(
+            &self,
+            object_id: u64,
+        ) -> Vec<u64> {
+            match self.residents.get(&object_id) {
+                None => vec![],
+                Some(residents) => residents.deref().clone(),
+            }
+        }
+
+        // The host proof can be a proof of the house itself, if it isn't rent, or of the Radixian renting it.
+        // System houses have no NFT, only the Radixian renting them can host
+        pub fn move_in(
             &self,
             people_proof: Proof,
-            name: String,
+            host_proof: Proof,
             object_id: u64,
         ) {
             let non_fungible = people_proof.check_with_message(
@@ -920,53 +3142,81 @@ mod radix_life {
             )
             .as_non_fungible()
             .non_fungible::<PeopleData>();
-            let people_id = match non_fungible.local_id() {
-                NonFungibleLocalId::Integer(id) => id.value(),
-                _ => Runtime::panic("Should not happen".to_string()),
-            };
+            let people_id = RadixLife::get_u64_id(non_fungible.local_id());
+            assert!(
+                self.retired_people.get(&people_id).is_none(),
+                "Radixian retired",
+            );
 
+            let nf_object_id = NonFungibleLocalId::integer(object_id.into());
+            let (name, rent_to, capacity) = match self.object_resource_manager.non_fungible_exists(&nf_object_id) {
+                true => {
+                    let object_data = self.object_resource_manager.get_non_fungible_data::<ObjectData>(&nf_object_id);
+                    let capacity = self.object_types.get(&object_data.name).expect("Object not found").capacity;
+                    (object_data.name, object_data.rent_to, capacity)
+                },
+                false => {
+                    let system_object = self.system_objects.get(&object_id).expect("Object not found");
+                    (system_object.name.clone(), system_object.rent_to, system_object.capacity)
+                },
+            };
             let object_type = self.object_types.get(&name).expect("Object not found");
             assert!(
-                object_type.can_be_rent,
-                "This object can't be rent",
+                object_type.category == ObjectCategory::House,
+                "Not a house",
             );
+            drop(object_type);
 
-            let nf_object_id = NonFungibleLocalId::Integer(object_id.into());
-
-            // If is possible to create offchain objects, without minting and NFT, and rent them
-            if self.object_resource_manager.non_fungible_exists(&nf_object_id) {
-                let non_fungible_data = self.object_resource_manager.get_non_fungible_data::<ObjectData>(&nf_object_id);
-                assert!(
-                    non_fungible_data.rent_allowed,
-                    "Object not for rent",
+            if host_proof.resource_address() == self.object_resource_manager.address() {
+                let host_id = RadixLife::get_u64_id(
+                    &host_proof.check(self.object_resource_manager.address())
+                    .as_non_fungible()
+                    .non_fungible_local_id()
                 );
                 assert!(
-                    non_fungible_data.rent_to == 0,
-                    "Object already rent",
+                    host_id == object_id && rent_to == 0,
+                    "Not allowed",
                 );
-                assert!(
-                    name == non_fungible_data.name,
-                    "Wrong name"
+            } else {
+                let host_id = RadixLife::get_u64_id(
+                    &host_proof.check_with_message(
+                        self.people_resource_manager.address(),
+                        "Wrong NFT",
+                    )
+                    .as_non_fungible()
+                    .non_fungible_local_id()
                 );
-
-                self.object_resource_manager.update_non_fungible_data(
-                    &nf_object_id,
-                    "rent_to",
-                    people_id,
+                assert!(
+                    rent_to != 0 && host_id == rent_to,
+                    "Not allowed",
                 );
             }
 
-            Runtime::emit_event(
-                RentEvent {
-                    object_id: object_id,
-                    people_id: people_id,
-                }
+            assert!(
+                (self.get_residents(object_id).len() as u32) < capacity,
+                "House full",
             );
+
+            self.set_residence(people_id, object_id);
         }
 
-        pub fn terminate_rent(
+        pub fn move_out(
             &self,
             people_proof: Proof,
+        ) {
+            let non_fungible = people_proof.check_with_message(
+                self.people_resource_manager.address(),
+                "Wrong NFT",
+            )
+            .as_non_fungible()
+            .non_fungible::<PeopleData>();
+
+            self.set_residence(RadixLife::get_u64_id(non_fungible.local_id()), 0);
+        }
+
+        pub fn terminate_rent(
+            &mut self,
+            people_proof: Proof,
             object_id: u64,
         ) {
             let non_fungible = people_proof.check_with_message(
@@ -980,21 +3230,58 @@ mod radix_life {
                 _ => Runtime::panic("Should not happen".to_string()),
             };
 
+            let (rent_to, _) = self.get_rent_terms(object_id);
+            assert!(
+                rent_to == people_id,
+                "Object not rent to you",
+            );
+
+            self.end_rent(object_id);
+        }
+
+        // Anyone can evict the renter of an object whose paid period is over
+        pub fn evict(
+            &mut self,
+            object_id: u64,
+        ) {
+            let (rent_to, _) = self.get_rent_terms(object_id);
+            assert!(
+                rent_to != 0,
+                "Object not rent",
+            );
+
+            let paid_until = *self.rent_paid_until.get(&object_id).expect("Object not rent");
+            assert!(
+                Clock::current_time_is_strictly_after(paid_until, TimePrecision::Second),
+                "Rent not expired",
+            );
+
+            self.end_rent(object_id);
+        }
+
+        fn end_rent(
+            &mut self,
+            object_id: u64,
+        ) {
             let nf_object_id = NonFungibleLocalId::Integer(object_id.into());
 
-            // If is possible to create offchain objects, without minting and NFT, and rent them
             if self.object_resource_manager.non_fungible_exists(&nf_object_id) {
                 let non_fungible_data = self.object_resource_manager.get_non_fungible_data::<ObjectData>(&nf_object_id);
-                assert!(
-                    non_fungible_data.rent_to == people_id,
-                    "Object not rent to you",
-                );
+                self.update_object_condition(&nf_object_id, &non_fungible_data);
 
                 self.object_resource_manager.update_non_fungible_data(
                     &nf_object_id,
                     "rent_to",
                     0,
                 );
+            } else {
+                self.system_objects.get_mut(&object_id).expect("Object not found").rent_to = 0;
+            }
+
+            self.rent_paid_until.remove(&object_id);
+
+            for resident in self.get_residents(object_id) {
+                self.set_residence(resident, 0);
             }
 
             Runtime::emit_event(
@@ -1024,6 +3311,10 @@ mod radix_life {
                 !non_fungible_data.rent_allowed && non_fungible_data.rent_to == 0,
                 "Can't sell rented object",
             );
+            assert!(
+                self.residents.get(&object_id).is_none(),
+                "Can't sell an inhabited house",
+            );
 
             self.used_objects_vault.put(object_bucket);
 
@@ -1050,23 +3341,22 @@ mod radix_life {
         pub fn buy_used_object(
             &mut self,
             receipt_id: u64,
-            owner: u64,
+            owner_proof: Option<Proof>,
             mut coin_bucket: Bucket,
         ) -> (
             NonFungibleBucket,
             Bucket,
         ) {
-            assert!(
-                coin_bucket.resource_address() == self.coin_resource_manager.address(),
-                "Wrong coin",
-            );
+            let owner = self.get_owner_id(owner_proof);
 
             let nf_receipt_id = NonFungibleLocalId::integer(receipt_id);
             let non_fungible_data = self.sold_objects_resource_manager.get_non_fungible_data::<SoldObjectReceipt>(
                 &nf_receipt_id
             );
 
-            coin_bucket.take(Decimal::from(non_fungible_data.price)).burn();
+            self.take_payment(&mut coin_bucket, non_fungible_data.price, CoinSource::Market);
+
+            self.set_object_owner(non_fungible_data.object_id, owner);
 
             Runtime::emit_event(
                 BoughtObjectEvent {
@@ -1083,63 +3373,361 @@ mod radix_life {
             )
         }
 
-        pub fn close_object_sale(
-            &mut self,
-            sold_object_bucket: Bucket,
-        ) -> Bucket {
+        pub fn close_object_sale(
+            &mut self,
+            sold_object_bucket: Bucket,
+        ) -> Bucket {
+            assert!(
+                sold_object_bucket.resource_address() == self.sold_objects_resource_manager.address(),
+                "Wrong NFT",
+            );
+            let non_fungible = sold_object_bucket.as_non_fungible().non_fungible::<SoldObjectReceipt>();
+            let non_fungible_data = non_fungible.data();
+
+            let nf_object_id = NonFungibleLocalId::integer(non_fungible_data.object_id);
+
+            sold_object_bucket.burn();
+
+            match self.used_objects_vault.contains_non_fungible(&nf_object_id) {
+                false => self.mint_coins(Decimal::from(non_fungible_data.price), CoinSource::Market).into(),
+                true => self.used_objects_vault.take_non_fungible(&nf_object_id).into(),
+            }
+        }
+
+        pub fn add_choice(
+            &mut self,
+            choice: String,
+            price: Option<u32>,
+            restored_needs: Vec<Need>,
+            work: bool,
+            random: bool,
+            requires_home: bool,
+        ) {
+            match price {
+                None => {
+                    self.choices.remove(&choice);
+                },
+                Some(price) => self.choices.insert(
+                    choice,
+                    ChoiceData {
+                        price: price,
+                        restored_needs: restored_needs,
+                        work: work,
+                        random: random,
+                        requires_home: requires_home,
+                    }
+                ),
+            }
+        }
+
+        pub fn update_needs_intervals(
+            &mut self,
+            meal_interval: i64,
+            rest_interval: i64,
+        ) {
+            assert!(
+                meal_interval > 0 && rest_interval > 0,
+                "Intervals must be bigger than zero",
+            );
+
+            self.meal_interval = meal_interval;
+            self.rest_interval = rest_interval;
+        }
+
+        pub fn refresh_needs(
+            &self,
+            people_id: u64,
+        ) {
+            assert!(
+                self.people_resource_manager.non_fungible_exists(&NonFungibleLocalId::integer(people_id.into())),
+                "Radixian not found",
+            );
+
+            self.update_people_needs(people_id, &[]);
+        }
+
+        // Health degrades with the time since the last meal and mood with the time since the last rest;
+        // every elapsed interval moves the status one step down
+        fn update_people_needs(
+            &self,
+            people_id: u64,
+            restored_needs: &[Need],
+        ) -> HealthStatus {
+            let id = NonFungibleLocalId::integer(people_id.into());
+            let people_data = self.people_resource_manager.get_non_fungible_data::<PeopleData>(&id);
+            let now = Clock::current_time_rounded_to_seconds();
+
+            let mut needs = self.people_needs.get(&people_id).expect("Needs not found").deref().clone();
+            for need in restored_needs.iter() {
+                match need {
+                    Need::Food => needs.last_meal = now,
+                    Need::Rest => needs.last_rest = now,
+                }
+            }
+            if !restored_needs.is_empty() {
+                self.people_needs.insert(people_id, needs.clone());
+            }
+
+            let hungry_intervals = (now.seconds_since_unix_epoch - needs.last_meal.seconds_since_unix_epoch).max(0) / self.meal_interval;
+            let health_status = match hungry_intervals {
+                0 => HealthStatus::Healthy,
+                1 => HealthStatus::Weak,
+                2 => HealthStatus::Sick,
+                _ => HealthStatus::Critical,
+            };
+
+            let tired_intervals = (now.seconds_since_unix_epoch - needs.last_rest.seconds_since_unix_epoch).max(0) / self.rest_interval;
+            let mood_status = match tired_intervals {
+                0 => MoodStatus::Happy,
+                1 => MoodStatus::Normal,
+                2 => MoodStatus::Sad,
+                _ => MoodStatus::Depressed,
+            };
+
+            if health_status == people_data.health_status && mood_status == people_data.mood_status && restored_needs.is_empty() {
+                return health_status;
+            }
+
+            if health_status != people_data.health_status {
+                self.people_resource_manager.update_non_fungible_data(&id, "health_status", health_status);
+            }
+            if mood_status != people_data.mood_status {
+                self.people_resource_manager.update_non_fungible_data(&id, "mood_status", mood_status);
+            }
+
+            Runtime::emit_event(
+                NeedsChangedEvent {
+                    people_id: people_id,
+                    last_meal: needs.last_meal.seconds_since_unix_epoch,
+                    last_rest: needs.last_rest.seconds_since_unix_epoch,
+                    health_status: health_status,
+                    mood_status: mood_status,
+                }
+            );
+
+            health_status
+        }
+
+        pub fn update_object_type(
+            &mut self,
+            name: String,
+            price: u32,
+            can_be_bought: bool,
+            can_be_mortgaged: bool,
+            can_be_rent: bool,
+        ) {
+            let mut object_type = self.object_types.get_mut(&name).expect("Object not found");
+
+            object_type.price = price;
+            object_type.can_be_bought = can_be_bought;
+            object_type.can_be_mortgaged = can_be_mortgaged;
+            object_type.can_be_rent = can_be_rent;
+
+            RadixLife::emit_object_type_updated(name, &object_type, vec![]);
+        }
+
+        // The image of the already minted objects listed in propagate_to is updated too; the list can be
+        // split across multiple calls with the same image if there are too many objects for a transaction
+        pub fn update_object_type_image(
+            &mut self,
+            name: String,
+            key_image_url: String,
+            propagate_to: Vec<u64>,
+        ) {
+            let mut object_type = self.object_types.get_mut(&name).expect("Object not found");
+            object_type.key_image_url = UncheckedUrl(key_image_url);
+
+            for object_id in propagate_to.iter() {
+                let nf_object_id = NonFungibleLocalId::integer((*object_id).into());
+                let non_fungible_data = self.object_resource_manager.get_non_fungible_data::<ObjectData>(&nf_object_id);
+                assert!(
+                    non_fungible_data.name == name,
+                    "Wrong object type",
+                );
+
+                self.object_resource_manager.update_non_fungible_data(
+                    &nf_object_id,
+                    "key_image_url",
+                    object_type.key_image_url.clone(),
+                );
+            }
+
+            RadixLife::emit_object_type_updated(name, &object_type, propagate_to);
+        }
+
+        // A retired object type can't be minted anymore but the existing objects keep working
+        pub fn retire_object_type(
+            &mut self,
+            name: String,
+        ) {
+            let mut object_type = self.object_types.get_mut(&name).expect("Object not found");
+            assert!(
+                !object_type.retired,
+                "Object type already retired",
+            );
+            object_type.retired = true;
+            object_type.can_be_bought = false;
+
+            RadixLife::emit_object_type_updated(name, &object_type, vec![]);
+        }
+
+        fn emit_object_type_updated(
+            name: String,
+            object_type: &ObjectTypeData,
+            updated_object_ids: Vec<u64>,
+        ) {
+            Runtime::emit_event(
+                ObjectTypeUpdatedEvent {
+                    name: name,
+                    price: object_type.price,
+                    key_image_url: object_type.key_image_url.0.clone(),
+                    can_be_bought: object_type.can_be_bought,
+                    can_be_mortgaged: object_type.can_be_mortgaged,
+                    can_be_rent: object_type.can_be_rent,
+                    retired: object_type.retired,
+                    updated_object_ids: updated_object_ids,
+                }
+            );
+        }
+
+        // A None price removes the recipe
+        pub fn add_recipe(
+            &mut self,
+            recipe: String,
+            inputs: Vec<(String, u32)>,
+            price: Option<u32>,
+            output: String,
+        ) {
+            match price {
+                None => {
+                    self.recipes.remove(&recipe);
+                },
+                Some(price) => {
+                    assert!(
+                        !inputs.is_empty(),
+                        "No inputs",
+                    );
+                    for (name, amount) in inputs.iter() {
+                        assert!(
+                            self.object_types.get(name).is_some(),
+                            "Object not found",
+                        );
+                        assert!(
+                            *amount > 0,
+                            "Invalid amount",
+                        );
+                    }
+                    assert!(
+                        self.object_types.get(&output).is_some(),
+                        "Object not found",
+                    );
+
+                    self.recipes.insert(
+                        recipe,
+                        RecipeData {
+                            inputs: inputs,
+                            price: price,
+                            output: output,
+                        }
+                    );
+                },
+            }
+        }
+
+        // Burns exactly the objects required by the recipe plus its price and mints the output object
+        pub fn craft(
+            &mut self,
+            recipe: String,
+            objects_bucket: NonFungibleBucket,
+            mut coin_bucket: Bucket,
+            owner_proof: Option<Proof>,
+        ) -> (
+            NonFungibleBucket,
+            Bucket,
+        ) {
+            assert!(
+                objects_bucket.resource_address() == self.object_resource_manager.address(),
+                "Wrong NFT",
+            );
+
+            let owner = self.get_owner_id(owner_proof);
+
+            let recipe_data = self.recipes.get(&recipe).expect("Recipe not found");
+            let price = recipe_data.price;
+            let output = recipe_data.output.clone();
+
+            let mut required: Vec<(String, u32)> = vec![];
+            for (name, amount) in recipe_data.inputs.iter() {
+                match required.iter_mut().find(|(required_name, _)| required_name == name) {
+                    None => required.push((name.clone(), *amount)),
+                    Some((_, required_amount)) => *required_amount += amount,
+                }
+            }
+            drop(recipe_data);
+
+            let mut input_ids: Vec<u64> = vec![];
+            for non_fungible in objects_bucket.non_fungibles::<ObjectData>() {
+                let non_fungible_data = non_fungible.data();
+                assert!(
+                    !non_fungible_data.rent_allowed && non_fungible_data.rent_to == 0,
+                    "Can't craft with rented objects",
+                );
+                assert!(
+                    self.residents.get(&RadixLife::get_u64_id(non_fungible.local_id())).is_none(),
+                    "Can't craft with inhabited houses",
+                );
+
+                let (_, amount) = required.iter_mut()
+                    .find(|(name, _)| *name == non_fungible_data.name)
+                    .expect("Object not required");
+                assert!(
+                    *amount > 0,
+                    "Too many objects",
+                );
+                *amount -= 1;
+
+                input_ids.push(RadixLife::get_u64_id(non_fungible.local_id()));
+            }
             assert!(
-                sold_object_bucket.resource_address() == self.sold_objects_resource_manager.address(),
-                "Wrong NFT",
+                required.iter().all(|(_, amount)| *amount == 0),
+                "Missing objects",
             );
-            let non_fungible = sold_object_bucket.as_non_fungible().non_fungible::<SoldObjectReceipt>();
-            let non_fungible_data = non_fungible.data();
-
-            let nf_object_id = NonFungibleLocalId::integer(non_fungible_data.object_id);
 
-            sold_object_bucket.burn();
+            self.take_payment(&mut coin_bucket, price, CoinSource::Craft);
 
-            match self.used_objects_vault.contains_non_fungible(&nf_object_id) {
-                false => self.coin_resource_manager.mint(non_fungible_data.price).into(),
-                true => self.used_objects_vault.take_non_fungible(&nf_object_id).into(),
+            for object_id in input_ids.iter() {
+                self.set_object_owner(*object_id, 0);
             }
-        }
+            objects_bucket.burn();
 
-        pub fn add_choice(
-            &mut self,
-            choice: String,
-            price: Option<u32>,
-        ) {
-            match price {
-                None => {
-                    self.choices.remove(&choice);
-                },
-                Some(price) => self.choices.insert(choice, price),
-            }
-        }
+            let output_bucket = self.mint_objects(output, 1, owner);
+            let output_ids = output_bucket.non_fungible_local_ids().iter()
+                .map(RadixLife::get_u64_id)
+                .collect();
 
-        pub fn update_object_type(
-            &mut self,
-            name: String,
-            price: u32,
-            can_be_bought: bool,
-            can_be_mortgaged: bool,
-            can_be_rent: bool,
-        ) {
-            let mut object_type = self.object_types.get_mut(&name).expect("Object not found");
+            Runtime::emit_event(
+                CraftedEvent {
+                    recipe: recipe,
+                    input_ids: input_ids,
+                    output_ids: output_ids,
+                    price: price,
+                    owner: owner,
+                }
+            );
 
-            object_type.price = price;
-            object_type.can_be_bought = can_be_bought;
-            object_type.can_be_mortgaged = can_be_mortgaged;
-            object_type.can_be_rent = can_be_rent;
+            (
+                output_bucket,
+                coin_bucket,
+            )
         }
 
         pub fn make_choice(
-            &self,
+            &mut self,
             people_proof: Proof,
             choice: String,
-            coin_bucket: Option<Bucket>,
+            mut coin_bucket: Option<Bucket>,
             number: u64,
-        ) {
+        ) -> Option<Bucket> {
             let non_fungible = people_proof.check_with_message(
                 self.people_resource_manager.address(),
                 "Wrong NFT",
@@ -1151,32 +3739,47 @@ mod radix_life {
                 _ => Runtime::panic("Should not happen".to_string()),
             };
 
-            let price_ref = self.choices.get(&choice).expect("Choice not found");
-            let price = price_ref.deref();
+            let choice_data = self.choices.get(&choice).expect("Choice not found");
+            let price = choice_data.price;
+            let random = choice_data.random;
+
+            assert!(
+                !choice_data.requires_home || non_fungible.data().residence != 0,
+                "A home is required",
+            );
+
+            let health_status = self.update_people_needs(people_id, &choice_data.restored_needs);
+            assert!(
+                !choice_data.work || !health_status.is_low(),
+                "Radixian health too low",
+            );
+            drop(choice_data);
 
             Runtime::emit_event(
                 ChoiceEvent {
-                    choice: choice,
+                    choice: choice.clone(),
                     people_id: people_id,
                     number: number,
                 }
             );
 
-            if *price > 0 {
-                let coin_bucket = coin_bucket.expect("Missing payment");
-
-                assert!(
-                    coin_bucket.resource_address() == self.coin_resource_manager.address(),
-                    "Wrong coin"
-                );
-
-                assert!(
-                    coin_bucket.amount() >= Decimal::from(*price),
-                    "Not enough coins",
+            if random {
+                self.request_random(
+                    people_id,
+                    RandomRequest::Choice {
+                        choice: choice,
+                        people_id: people_id,
+                        number: number,
+                    }
                 );
+            }
 
-                coin_bucket.burn();
+            if price > 0 {
+                let payment_bucket = coin_bucket.as_mut().expect("Missing payment");
+                self.take_payment(payment_bucket, price, CoinSource::Choice);
             }
+
+            coin_bucket
         }
 
         pub fn sell_people(
@@ -1224,17 +3827,12 @@ mod radix_life {
             NonFungibleBucket,
             Bucket,
         ) {
-            assert!(
-                coin_bucket.resource_address() == self.coin_resource_manager.address(),
-                "Wrong coin",
-            );
-
             let nf_receipt_id = NonFungibleLocalId::integer(receipt_id);
             let non_fungible_data = self.sold_people_resource_manager.get_non_fungible_data::<SoldPeopleReceipt>(
                 &nf_receipt_id
             );
 
-            coin_bucket.take(Decimal::from(non_fungible_data.price)).burn();
+            self.take_payment(&mut coin_bucket, non_fungible_data.price, CoinSource::Market);
 
             Runtime::emit_event(
                 BoughtPeopleEvent {
@@ -1265,17 +3863,166 @@ mod radix_life {
             sold_people_bucket.burn();
 
             match self.people_vault.contains_non_fungible(&nf_people_id) {
-                false => self.coin_resource_manager.mint(non_fungible_data.price).into(),
+                false => self.mint_coins(Decimal::from(non_fungible_data.price), CoinSource::Market).into(),
                 true => self.people_vault.take_non_fungible(&nf_people_id).into(),
             }
         }
 
+        pub fn update_reward_caps(
+            &mut self,
+            reward_call_cap: Option<u32>,
+            epoch_reward_cap: Option<u32>,
+            daily_reward_cap: Option<u32>,
+        ) {
+            self.reward_call_cap = reward_call_cap;
+            self.epoch_reward_cap = epoch_reward_cap;
+            self.daily_reward_cap = daily_reward_cap;
+        }
+
+        fn mint_coins(
+            &self,
+            amount: Decimal,
+            source: CoinSource,
+        ) -> FungibleBucket {
+            let minted = match self.minted_coins.get(&source) {
+                None => Decimal::ZERO,
+                Some(minted) => *minted,
+            };
+            self.minted_coins.insert(source, minted + amount);
+
+            self.coin_resource_manager.mint(amount)
+        }
+
+        fn burn_coins(
+            &self,
+            coin_bucket: Bucket,
+            source: CoinSource,
+        ) {
+            let burned = match self.burned_coins.get(&source) {
+                None => Decimal::ZERO,
+                Some(burned) => *burned,
+            };
+            self.burned_coins.insert(source, burned + coin_bucket.amount());
+
+            coin_bucket.burn();
+        }
+
+        // Payments can be made in coins, which are burned, or in XRD at the current coin price, which
+        // are kept in the XRD vault; the change stays in the bucket, in the same currency
+        fn take_payment(
+            &mut self,
+            payment_bucket: &mut Bucket,
+            price: u32,
+            source: CoinSource,
+        ) {
+            if payment_bucket.resource_address() == XRD {
+                let xrd_amount = Decimal::from(price) * self.get_coin_xrd_price();
+                self.xrd_vault.put(payment_bucket.take(xrd_amount));
+            } else {
+                assert!(
+                    payment_bucket.resource_address() == self.coin_resource_manager.address(),
+                    "Wrong coin",
+                );
+                self.burn_coins(payment_bucket.take(Decimal::from(price)), source);
+            }
+        }
+
+        pub fn get_coin_supply(
+            &self,
+        ) -> CoinSupply {
+            let minted: Vec<(CoinSource, Decimal)> = COIN_SOURCES.iter()
+                .map(|source| (*source, self.minted_coins.get(source).map(|minted| *minted).unwrap_or_default()))
+                .collect();
+            let burned: Vec<(CoinSource, Decimal)> = COIN_SOURCES.iter()
+                .map(|source| (*source, self.burned_coins.get(source).map(|burned| *burned).unwrap_or_default()))
+                .collect();
+            let circulating = minted.iter().fold(Decimal::ZERO, |total, (_, amount)| total + *amount)
+                - burned.iter().fold(Decimal::ZERO, |total, (_, amount)| total + *amount);
+
+            CoinSupply {
+                minted: minted,
+                burned: burned,
+                circulating: circulating,
+            }
+        }
+
+        // Every coin minted by send_coins and send_coins_batch counts towards the caps
+        fn register_reward(
+            &mut self,
+            amount: u32,
+        ) {
+            if let Some(reward_call_cap) = self.reward_call_cap {
+                assert!(
+                    amount <= reward_call_cap,
+                    "Reward cap exceeded",
+                );
+            }
+
+            let epoch = Runtime::current_epoch().number();
+            if epoch != self.reward_epoch {
+                self.reward_epoch = epoch;
+                self.rewarded_this_epoch = 0;
+            }
+            self.rewarded_this_epoch += amount;
+            if let Some(epoch_reward_cap) = self.epoch_reward_cap {
+                assert!(
+                    self.rewarded_this_epoch <= epoch_reward_cap,
+                    "Epoch reward cap exceeded",
+                );
+            }
+
+            let day = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch / 86400;
+            if day != self.reward_day {
+                self.reward_day = day;
+                self.rewarded_today = 0;
+            }
+            self.rewarded_today += amount;
+            if let Some(daily_reward_cap) = self.daily_reward_cap {
+                assert!(
+                    self.rewarded_today <= daily_reward_cap,
+                    "Daily reward cap exceeded",
+                );
+            }
+        }
+
+        // Zero when the reward isn't for a Radixian
+        fn check_rewarded_people(
+            &self,
+            people_id: Option<u64>,
+        ) -> u64 {
+            match people_id {
+                None => 0,
+                Some(people_id) => {
+                    assert!(
+                        self.people_resource_manager.non_fungible_exists(&NonFungibleLocalId::integer(people_id.into())),
+                        "Radixian not found",
+                    );
+                    people_id
+                },
+            }
+        }
+
+        // The reason is a code defined by the backend, people_id is the Radixian being rewarded, if any
         pub fn send_coins(
             &mut self,
             amount: u32,
             account: Global<Account>,
+            reason: u16,
+            people_id: Option<u64>,
         ) {
-            let coin_bucket = self.coin_resource_manager.mint(amount);
+            self.register_reward(amount);
+            let people_id = self.check_rewarded_people(people_id);
+
+            Runtime::emit_event(
+                CoinsSentEvent {
+                    account: account.address(),
+                    amount: amount,
+                    reason: reason,
+                    people_id: people_id,
+                }
+            );
+
+            let coin_bucket = self.mint_coins(Decimal::from(amount), CoinSource::Reward);
 
             self.account_locker.store(
                 account,
@@ -1283,5 +4030,241 @@ mod radix_life {
                 true,
             );
         }
+
+        // A single airdrop for all of the recipients; the caps apply to every payout
+        pub fn send_coins_batch(
+            &mut self,
+            payouts: Vec<(Global<Account>, u32, Option<u64>)>,
+            reason: u16,
+        ) {
+            let mut claimants: IndexMap<Global<Account>, Decimal> = index_map_new();
+            let mut total: u32 = 0;
+            for (account, amount, people_id) in payouts {
+                self.register_reward(amount);
+                let people_id = self.check_rewarded_people(people_id);
+                total += amount;
+
+                Runtime::emit_event(
+                    CoinsSentEvent {
+                        account: account.address(),
+                        amount: amount,
+                        reason: reason,
+                        people_id: people_id,
+                    }
+                );
+
+                let claimed = claimants.get(&account).copied().unwrap_or_default();
+                claimants.insert(account, claimed + Decimal::from(amount));
+            }
+
+            let coin_bucket = self.mint_coins(Decimal::from(total), CoinSource::Reward);
+
+            self.account_locker.airdrop(
+                claimants.into_iter()
+                    .map(|(account, amount)| (account, ResourceSpecifier::Fungible(amount)))
+                    .collect(),
+                coin_bucket.into(),
+                true,
+            );
+        }
+
+        pub fn transfer_object_owner(
+            &self,
+            object_proof: Proof,
+            owner_proof: Option<Proof>,
+        ) {
+            let non_fungible = object_proof.check_with_message(
+                self.object_resource_manager.address(),
+                "Wrong NFT",
+            )
+            .as_non_fungible()
+            .non_fungible::<ObjectData>();
+            let object_id = RadixLife::get_u64_id(non_fungible.local_id());
+
+            let owner = self.get_owner_id(owner_proof);
+            self.set_object_owner(object_id, owner);
+
+            Runtime::emit_event(
+                ObjectOwnerEvent {
+                    object_id: object_id,
+                    owner: owner,
+                }
+            );
+        }
+
+        pub fn get_object_owner(
+            &self,
+            object_id: u64,
+        ) -> u64 {
+            match self.object_owners.get(&object_id) {
+                None => 0,
+                Some(owner) => *owner,
+            }
+        }
+
+        pub fn get_owned_objects(
+            &self,
+            people_id: u64,
+        ) -> Vec<u64> {
+            match self.owned_objects.get(&people_id) {
+                None => vec![],
+                Some(object_ids) => object_ids.deref().clone(),
+            }
+        }
+
+        pub fn set_heir(
+            &self,
+            people_proof: Proof,
+            heir: Option<u64>,
+        ) {
+            let non_fungible = people_proof.check_with_message(
+                self.people_resource_manager.address(),
+                "Wrong NFT",
+            )
+            .as_non_fungible()
+            .non_fungible::<PeopleData>();
+            let people_id = RadixLife::get_u64_id(non_fungible.local_id());
+
+            assert!(
+                self.retired_people.get(&people_id).is_none(),
+                "Radixian retired",
+            );
+
+            match heir {
+                None => {
+                    self.heirs.remove(&people_id);
+                },
+                Some(heir) => {
+                    assert!(
+                        heir != people_id,
+                        "Can't be heir of yourself",
+                    );
+                    assert!(
+                        self.people_resource_manager.non_fungible_exists(&NonFungibleLocalId::integer(heir.into())),
+                        "Heir not found",
+                    );
+                    assert!(
+                        self.retired_people.get(&heir).is_none(),
+                        "Heir retired",
+                    );
+                    self.heirs.insert(people_id, heir);
+                },
+            }
+
+            Runtime::emit_event(
+                HeirEvent {
+                    people_id: people_id,
+                    heir: heir.unwrap_or(0),
+                }
+            );
+        }
+
+        pub fn retire_people(
+            &mut self,
+            people_proof: Proof,
+        ) {
+            let non_fungible = people_proof.check_with_message(
+                self.people_resource_manager.address(),
+                "Wrong NFT",
+            )
+            .as_non_fungible()
+            .non_fungible::<PeopleData>();
+
+            self.inherit(RadixLife::get_u64_id(non_fungible.local_id()));
+        }
+
+        pub fn execute_inheritance(
+            &mut self,
+            people_id: u64,
+        ) {
+            assert!(
+                self.people_resource_manager.non_fungible_exists(&NonFungibleLocalId::integer(people_id.into())),
+                "Radixian not found",
+            );
+
+            self.inherit(people_id);
+        }
+
+        // The designated heir if still active, otherwise the first active child.
+        // Zero if nobody can inherit
+        fn find_heir(
+            &self,
+            people_id: u64,
+        ) -> u64 {
+            if let Some(heir) = self.heirs.get(&people_id) {
+                if self.retired_people.get(&*heir).is_none() {
+                    return *heir;
+                }
+            }
+
+            self.get_children(people_id).into_iter()
+                .find(|child| self.retired_people.get(child).is_none())
+                .unwrap_or(0)
+        }
+
+        fn inherit(
+            &mut self,
+            people_id: u64,
+        ) {
+            assert!(
+                self.retired_people.get(&people_id).is_none(),
+                "Radixian already retired",
+            );
+            self.retired_people.insert(people_id, Clock::current_time_rounded_to_seconds());
+
+            let heir = self.find_heir(people_id);
+            self.heirs.remove(&people_id);
+            self.set_residence(people_id, 0);
+
+            self.accrue_interest(people_id);
+            self.interest_accrued_at.remove(&people_id);
+            self.interest_remainders.remove(&people_id);
+            let amount = self.bank_accounts.remove(&people_id).unwrap_or(0);
+            let object_ids = self.owned_objects.remove(&people_id).unwrap_or_default();
+            let loan_ids = self.people_loans.remove(&people_id).unwrap_or_default();
+
+            // The heir takes over the open loans, whose collateral is inherited too; with no heir
+            // nobody can repay them and the collateral is seized right away
+            for loan_id in loan_ids.iter() {
+                match heir {
+                    0 => {
+                        let loan = self.loans.remove(loan_id).unwrap();
+                        self.seize_collateral(*loan_id, loan);
+                    },
+                    _ => {
+                        self.loans.get_mut(loan_id).unwrap().people_id = heir;
+                        RadixLife::add_to_list(&self.people_loans, heir, *loan_id);
+                    },
+                }
+            }
+
+            for object_id in object_ids.iter() {
+                match heir {
+                    0 => {
+                        self.object_owners.remove(object_id);
+                    },
+                    _ => {
+                        self.object_owners.insert(*object_id, heir);
+                        RadixLife::add_to_list(&self.owned_objects, heir, *object_id);
+                    },
+                }
+            }
+
+            if heir != 0 && amount > 0 {
+                self.accrue_interest(heir);
+                let balance = self.get_bank_balance(heir);
+                self.bank_accounts.insert(heir, balance + amount);
+            }
+
+            Runtime::emit_event(
+                InheritanceEvent {
+                    people_id: people_id,
+                    heir: heir,
+                    amount: amount,
+                    object_ids: object_ids,
+                    loan_ids: loan_ids,
+                }
+            );
+        }
     }
 }
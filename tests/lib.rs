@@ -1,68 +1,1744 @@
 use scrypto_test::prelude::*;
+use std::sync::{Mutex, OnceLock};
 
-use radix_life::hello_test::*;
+struct TestEnv {
+    ledger: DefaultLedgerSimulator,
+    public_key: Secp256k1PublicKey,
+    account: ComponentAddress,
+    owner_badge: ResourceAddress,
+    updater_badge: ResourceAddress,
+    mocks_package: PackageAddress,
+    random: ComponentAddress,
+    component: ComponentAddress,
+    people: ResourceAddress,
+    coin: ResourceAddress,
+    object: ResourceAddress,
+    sold_object: ResourceAddress,
+    last_people_id: u64,
+    round: u64,
+    now: i64,
+}
+
+const HOUSE: u8 = 0;
+const VEHICLE: u8 = 1;
+const TOOL: u8 = 2;
+const OTHER: u8 = 3;
+
+#[derive(ScryptoSbor, PartialEq, Debug)]
+enum ObjectCategory {
+    House,
+    Vehicle,
+    Tool,
+    Other,
+}
+
+#[derive(ScryptoSbor)]
+struct ObjectTypeData {
+    can_be_bought: bool,
+    can_be_mortgaged: bool,
+    can_be_rent: bool,
+    price: u32,
+    key_image_url: UncheckedUrl,
+    max_supply: Option<u32>,
+    minted: u32,
+    retired: bool,
+    daily_decay: u8,
+    category: ObjectCategory,
+    capacity: u32,
+}
+
+#[derive(ScryptoSbor)]
+struct ObjectTypeAddedEvent {
+    name: String,
+    price: u32,
+    key_image_url: String,
+    can_be_bought: bool,
+    can_be_mortgaged: bool,
+    can_be_rent: bool,
+    max_supply: Option<u32>,
+    daily_decay: u8,
+    category: ObjectCategory,
+    capacity: u32,
+}
+
+#[derive(ScryptoSbor)]
+struct CoinsSentEvent {
+    account: ComponentAddress,
+    amount: u32,
+    reason: u16,
+    people_id: u64,
+}
+
+#[derive(ScryptoSbor)]
+struct PeopleFieldChange {
+    field: String,
+    old_value: String,
+    new_value: String,
+}
+
+#[derive(ScryptoSbor)]
+struct PeopleUpdatedEvent {
+    people_id: u64,
+    changes: Vec<PeopleFieldChange>,
+}
+
+#[derive(ScryptoSbor, NonFungibleData)]
+struct ObjectData {
+    name: String,
+    edition: u32,
+    max_supply: Option<u32>,
+    rent_allowed: bool,
+    daily_rent_price: u32,
+    rent_to: u64,
+    key_image_url: UncheckedUrl,
+    condition: u8,
+    condition_updated: Instant,
+}
+
+#[derive(ScryptoSbor)]
+struct NewObjectEvent {
+    name: String,
+    ids: Vec<u64>,
+    first_edition: u32,
+    owner: u64,
+}
+
+#[derive(ScryptoSbor)]
+struct ObjectTypeUpdatedEvent {
+    name: String,
+    price: u32,
+    key_image_url: String,
+    can_be_bought: bool,
+    can_be_mortgaged: bool,
+    can_be_rent: bool,
+    retired: bool,
+    updated_object_ids: Vec<u64>,
+}
+
+#[derive(ScryptoSbor)]
+struct ObjectConditionEvent {
+    object_id: u64,
+    condition: u8,
+}
+
+#[derive(ScryptoSbor)]
+struct RepairEvent {
+    object_id: u64,
+    cost: u32,
+}
+
+#[derive(ScryptoSbor)]
+struct CraftedEvent {
+    recipe: String,
+    input_ids: Vec<u64>,
+    output_ids: Vec<u64>,
+    price: u32,
+    owner: u64,
+}
+
+#[derive(ScryptoSbor)]
+struct BankTransferEvent {
+    from_people_id: u64,
+    to_people_id: u64,
+    amount: u32,
+}
+
+#[derive(ScryptoSbor, PartialEq, Debug)]
+enum CoinSource {
+    Xrd,
+    Bank,
+    Loan,
+    Reward,
+    Market,
+    Object,
+    Choice,
+    Rent,
+    Repair,
+    Craft,
+}
+
+#[derive(ScryptoSbor)]
+struct CoinSupply {
+    minted: Vec<(CoinSource, Decimal)>,
+    burned: Vec<(CoinSource, Decimal)>,
+    circulating: Decimal,
+}
+
+#[derive(ScryptoSbor)]
+struct SoldObjectEvent {
+    object_id: u64,
+    price: u32,
+    receipt_id: u64,
+}
+
+type CompiledPackage = (Vec<u8>, PackageDefinition);
+
+// Compiling the packages is slow: do it once for all the tests
+fn compiled_package(path: &str) -> CompiledPackage {
+    static PACKAGES: OnceLock<Mutex<HashMap<String, CompiledPackage>>> = OnceLock::new();
+    let mut packages = PACKAGES.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap();
+    packages.entry(path.to_string())
+        .or_insert_with(|| Compile::compile(path, CompileProfile::FastWithTraceLogs))
+        .clone()
+}
+
+#[derive(ScryptoSbor, ManifestSbor, NonFungibleData)]
+struct LegacyPeopleData {
+    name: String,
+    birth_date: Instant,
+    father: u64,
+    mother: u64,
+    gender: String,
+    occupation: String,
+    partner: u64,
+    mood_status: String,
+    health_status: String,
+    schooling: String,
+    key_image_url: UncheckedUrl,
+}
+
+#[derive(ScryptoSbor, PartialEq, Debug)]
+enum MoodStatus {
+    Happy,
+    Normal,
+    Sad,
+    Depressed,
+}
+
+#[derive(ScryptoSbor, PartialEq, Debug)]
+enum HealthStatus {
+    Healthy,
+    Weak,
+    Sick,
+    Critical,
+}
+
+#[derive(ScryptoSbor)]
+struct GenomeEvent {
+    key: u32,
+    people_id: u64,
+    seed: Vec<u8>,
+    genome: Vec<u8>,
+}
+
+#[derive(ManifestSbor)]
+enum Need {
+    Food,
+    Rest,
+}
+
+#[derive(ScryptoSbor)]
+struct NeedsChangedEvent {
+    people_id: u64,
+    last_meal: i64,
+    last_rest: i64,
+    health_status: HealthStatus,
+    mood_status: MoodStatus,
+}
+
+// ObjectCategory variants are passed by discriminator
+fn category(discriminator: u8) -> ManifestValue {
+    ManifestValue::Enum {
+        discriminator: discriminator,
+        fields: vec![],
+    }
+}
+
+impl TestEnv {
+    fn new() -> Self {
+        let mut ledger = LedgerSimulatorBuilder::new().build();
+        let (public_key, _private_key, account) = ledger.new_allocated_account();
+
+        let owner_badge = ledger.create_fungible_resource(dec!(1), 0, account);
+        let updater_badge = ledger.create_fungible_resource(dec!(1), 0, account);
+
+        let package_address = ledger.publish_package_simple(compiled_package(this_package!()));
+        let mocks_package = ledger.publish_package_simple(compiled_package(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/mocks")));
+
+        let receipt = ledger.execute_manifest(
+            ManifestBuilder::new()
+                .lock_fee_from_faucet()
+                .call_function(mocks_package, "MockRandom", "new", manifest_args!())
+                .build(),
+            vec![],
+        );
+        let random = receipt.expect_commit_success().new_component_addresses()[0];
+
+        let receipt = ledger.execute_manifest(
+            ManifestBuilder::new()
+                .lock_fee_from_faucet()
+                .call_function(
+                    package_address,
+                    "RadixLife",
+                    "new",
+                    manifest_args!(
+                        owner_badge,
+                        updater_badge,
+                        100u32,
+                        dec!(10),
+                        dec!(1),
+                        0i64,
+                        "https://radixlife.test/egg.png".to_string(),
+                    ),
+                )
+                .build(),
+            vec![],
+        );
+        let commit = receipt.expect_commit_success();
+        let component: ComponentAddress = commit.output(1);
+        let new_resources = commit.new_resource_addresses().clone();
+
+        let find_resource = |ledger: &mut DefaultLedgerSimulator, name: &str| -> ResourceAddress {
+            *new_resources.iter()
+                .find(|address| ledger.get_metadata((**address).into(), "name") == Some(MetadataValue::String(name.to_string())))
+                .unwrap()
+        };
+        let people = find_resource(&mut ledger, "Radixian");
+        let coin = find_resource(&mut ledger, "RadixLife coin");
+        let object = find_resource(&mut ledger, "RadixLife object");
+        let sold_object = find_resource(&mut ledger, "RadixLife object on sale");
+
+        let mut env = Self {
+            ledger: ledger,
+            public_key: public_key,
+            account: account,
+            owner_badge: owner_badge,
+            updater_badge: updater_badge,
+            mocks_package: mocks_package,
+            random: random,
+            component: component,
+            people: people,
+            coin: coin,
+            object: object,
+            sold_object: sold_object,
+            last_people_id: 0,
+            round: 0,
+            now: 0,
+        };
+        env.set_time(1_000_000);
+        env.call_owner("set_random_component", manifest_args!(Some(random)))
+            .expect_commit_success();
+
+        env
+    }
+
+    fn execute(&mut self, manifest: TransactionManifestV1) -> TransactionReceipt {
+        self.ledger.execute_manifest(
+            manifest,
+            vec![NonFungibleGlobalId::from_public_key(&self.public_key)],
+        )
+    }
+
+    fn set_time(&mut self, seconds: i64) {
+        self.now = seconds;
+        self.round += 1;
+        self.ledger.advance_to_round_at_timestamp(Round::of(self.round), seconds * 1000);
+    }
+
+    fn advance_days(&mut self, days: i64) {
+        self.set_time(self.now + days * 86400);
+    }
+
+    fn call_owner(&mut self, method: &str, args: ManifestArgs) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_amount(self.account, self.owner_badge, dec!(1))
+            .call_method(self.component, method, args)
+            .build();
+        self.execute(manifest)
+    }
+
+    fn call_updater(&mut self, method: &str, args: ManifestArgs) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_amount(self.account, self.updater_badge, dec!(1))
+            .call_method(self.component, method, args)
+            .build();
+        self.execute(manifest)
+    }
+
+    fn call_public_receipt(&mut self, method: &str, args: ManifestArgs) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(self.component, method, args)
+            .build();
+        self.execute(manifest)
+    }
+
+    fn call_public<T: ScryptoDecode>(&mut self, method: &str, args: ManifestArgs) -> T {
+        self.call_public_receipt(method, args).expect_commit_success().output(1)
+    }
+
+    fn add_object_type(&mut self, name: &str, price: u32, can_be_rent: bool, category_discriminator: u8, capacity: u32) {
+        self.call_owner(
+            "add_object_type",
+            manifest_args!(
+                name.to_string(),
+                price,
+                "https://radixlife.test/object.png".to_string(),
+                true,
+                true,
+                can_be_rent,
+                None::<u32>,
+                0u8,
+                category(category_discriminator),
+                capacity,
+            ),
+        ).expect_commit_success();
+    }
+
+    fn add_system_object(&mut self, name: &str, daily_rent_price: u32, capacity: u32) -> TransactionReceipt {
+        self.call_updater(
+            "add_system_object",
+            manifest_args!(name.to_string(), daily_rent_price, capacity),
+        )
+    }
+
+    fn resolve_random(&mut self, seed: u8) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(self.random, "execute", manifest_args!(vec![seed; 32]))
+            .build();
+        self.execute(manifest)
+    }
+
+    fn buy_egg(&mut self) -> u64 {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(self.account, XRD, dec!(10))
+            .take_all_from_worktop(XRD, "xrd")
+            .call_method_with_name_lookup(self.component, "buy_egg", |lookup| (lookup.bucket("xrd"),))
+            .call_method(self.account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
+            .build();
+        self.execute(manifest).expect_commit_success();
+        self.resolve_random(self.last_people_id as u8 + 1).expect_commit_success();
+
+        self.last_people_id += 1;
+        self.last_people_id
+    }
+
+    fn buy_coins(&mut self, amount: u32) {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(self.account, XRD, Decimal::from(amount))
+            .take_all_from_worktop(XRD, "xrd")
+            .call_method_with_name_lookup(self.component, "buy_coins", |lookup| (lookup.bucket("xrd"),))
+            .call_method(self.account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
+            .build();
+        self.execute(manifest).expect_commit_success();
+    }
+
+    fn deposit(&mut self, people_id: u64, amount: u32) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(self.account, self.coin, Decimal::from(amount))
+            .take_all_from_worktop(self.coin, "coins")
+            .call_method_with_name_lookup(
+                self.component,
+                "deposit_to_bank_account",
+                |lookup| (people_id, lookup.bucket("coins")),
+            )
+            .build();
+        self.execute(manifest)
+    }
+
+    fn bank_balance(&mut self, people_id: u64) -> u32 {
+        self.call_public("get_bank_balance", manifest_args!(people_id))
+    }
+
+    // Calls a method passing the proof of a Radixian as first argument
+    fn call_as_people(
+        &mut self,
+        people_id: u64,
+        method: &str,
+        coins: Option<u32>,
+        args: impl FnOnce(&ManifestNameLookup) -> ManifestArgs,
+    ) -> TransactionReceipt {
+        let mut builder = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_non_fungibles(
+                self.account,
+                self.people,
+                [NonFungibleLocalId::integer(people_id)],
+            )
+            .pop_from_auth_zone("people_proof");
+        if let Some(coins) = coins {
+            builder = builder
+                .withdraw_from_account(self.account, self.coin, Decimal::from(coins))
+                .take_all_from_worktop(self.coin, "coins");
+        }
+        let component = self.component;
+        let manifest = builder
+            .call_method_with_name_lookup(component, method, args)
+            .call_method(self.account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
+            .build();
+        self.execute(manifest)
+    }
+
+    // The host is either a Radixian renting the house or the house itself
+    fn move_in(&mut self, people_id: u64, host_resource: ResourceAddress, host_id: u64, object_id: u64) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_non_fungibles(
+                self.account,
+                self.people,
+                [NonFungibleLocalId::integer(people_id)],
+            )
+            .pop_from_auth_zone("people_proof")
+            .create_proof_from_account_of_non_fungibles(
+                self.account,
+                host_resource,
+                [NonFungibleLocalId::integer(host_id)],
+            )
+            .pop_from_auth_zone("host_proof")
+            .call_method_with_name_lookup(self.component, "move_in", |lookup| manifest_args!(
+                lookup.proof("people_proof"),
+                lookup.proof("host_proof"),
+                object_id,
+            ))
+            .build();
+        self.execute(manifest)
+    }
+
+    fn buy_objects(&mut self, name: &str, amount: u8, mortgaged: bool, owner: Option<u64>, coins: u32) -> TransactionReceipt {
+        let mut builder = ManifestBuilder::new()
+            .lock_fee_from_faucet();
+        if let Some(owner) = owner {
+            builder = builder
+                .create_proof_from_account_of_non_fungibles(
+                    self.account,
+                    self.people,
+                    [NonFungibleLocalId::integer(owner)],
+                )
+                .pop_from_auth_zone("owner_proof");
+        }
+        let manifest = builder
+            .withdraw_from_account(self.account, self.coin, Decimal::from(coins))
+            .take_all_from_worktop(self.coin, "coins")
+            .call_method_with_name_lookup(self.component, "buy_objects", |lookup| manifest_args!(
+                lookup.bucket("coins"),
+                name.to_string(),
+                amount,
+                mortgaged,
+                owner.map(|_| lookup.proof("owner_proof")),
+            ))
+            .call_method(self.account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
+            .build();
+        self.execute(manifest)
+    }
+
+    fn sell_object(&mut self, object_id: u64, price: u32) -> TransactionReceipt {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_non_fungibles_from_account(self.account, self.object, [NonFungibleLocalId::integer(object_id)])
+            .take_all_from_worktop(self.object, "object")
+            .call_method_with_name_lookup(self.component, "sell_object", |lookup| (lookup.bucket("object"), price))
+            .call_method(self.account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
+            .build();
+        self.execute(manifest)
+    }
+
+    fn object_data(&self, object_id: u64) -> ObjectData {
+        self.ledger.get_non_fungible_data(self.object, NonFungibleLocalId::integer(object_id))
+    }
+
+    fn event_data<T: ScryptoDecode>(&self, receipt: &TransactionReceipt, name: &str) -> T {
+        let (_, data) = receipt.expect_commit_success().application_events.iter()
+            .find(|(event_type_identifier, _)| self.ledger.event_name(event_type_identifier) == name)
+            .expect("Event not found");
+        scrypto_decode(data).unwrap()
+    }
+
+    fn has_event(&self, receipt: &TransactionReceipt, name: &str) -> bool {
+        receipt.expect_commit_success().application_events.iter()
+            .any(|(event_type_identifier, _)| self.ledger.event_name(event_type_identifier) == name)
+    }
+}
+
+#[test]
+fn test_egg_requires_randomness_component() {
+    let mut env = TestEnv::new();
+    env.call_owner("set_random_component", manifest_args!(None::<ComponentAddress>))
+        .expect_commit_success();
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(env.account, XRD, dec!(10))
+        .take_all_from_worktop(XRD, "xrd")
+        .call_method_with_name_lookup(env.component, "buy_egg", |lookup| (lookup.bucket("xrd"),))
+        .call_method(env.account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+    env.execute(manifest).expect_commit_failure();
+}
 
 #[test]
-fn test_hello() {
-    // Setup the environment
-    let mut ledger = LedgerSimulatorBuilder::new().build();
+fn test_egg_genome_from_random_callback() {
+    let mut env = TestEnv::new();
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(env.account, XRD, dec!(10))
+        .take_all_from_worktop(XRD, "xrd")
+        .call_method_with_name_lookup(env.component, "buy_egg", |lookup| (lookup.bucket("xrd"),))
+        .call_method(env.account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+    let receipt = env.execute(manifest);
+    assert!(env.has_event(&receipt, "RandomRequestEvent"));
+    assert!(!env.has_event(&receipt, "GenomeEvent"));
+
+    let receipt = env.resolve_random(42);
+    assert!(env.has_event(&receipt, "GenomeEvent"));
+
+    // The callback can only come from the randomness component
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(env.component, "random_callback", manifest_args!(1u32, vec![0u8; 32]))
+        .build();
+    env.execute(manifest).expect_commit_failure();
+}
+
+#[test]
+fn test_evict_after_rent_expires() {
+    let mut env = TestEnv::new();
+    let renter = env.buy_egg();
+    env.add_object_type("Flat", 100, true, HOUSE, 2);
+    let object_id: u64 = env.add_system_object("Flat", 0, 2).expect_commit_success().output(2);
+
+    env.call_as_people(renter, "rent", None, |lookup| manifest_args!(
+        lookup.proof("people_proof"),
+        "Flat".to_string(),
+        object_id,
+        1u32,
+        None::<ManifestBucket>,
+    )).expect_commit_success();
+    assert_eq!(env.call_public::<Vec<u64>>("get_residents", manifest_args!(object_id)), vec![renter]);
+
+    // The paid day isn't over yet
+    env.call_public_receipt("evict", manifest_args!(object_id)).expect_commit_failure();
+
+    env.advance_days(2);
+    let receipt = env.call_public_receipt("evict", manifest_args!(object_id));
+    assert!(env.has_event(&receipt, "TerminateRentEvent"));
+    assert!(env.call_public::<Vec<u64>>("get_residents", manifest_args!(object_id)).is_empty());
+
+    env.call_as_people(renter, "terminate_rent", None, |lookup| manifest_args!(
+        lookup.proof("people_proof"),
+        object_id,
+    )).expect_commit_failure();
+}
+
+#[test]
+fn test_system_object_capacity() {
+    let mut env = TestEnv::new();
+    env.add_object_type("Flat", 100, true, HOUSE, 2);
+    env.add_object_type("Bike", 10, true, OTHER, 0);
+
+    env.add_system_object("Flat", 1, 0).expect_commit_failure();
+    env.add_system_object("Flat", 1, 3).expect_commit_success();
+    env.add_system_object("Bike", 1, 3).expect_commit_failure();
+    let bike_id: u64 = env.add_system_object("Bike", 1, 0).expect_commit_success().output(2);
+
+    env.call_updater("update_system_object", manifest_args!(bike_id, 1u32, 1u32)).expect_commit_failure();
+
+    let renter = env.buy_egg();
+    let guest = env.buy_egg();
+    let flat_id: u64 = env.add_system_object("Flat", 0, 2).expect_commit_success().output(2);
+    env.call_as_people(renter, "rent", None, |lookup| manifest_args!(
+        lookup.proof("people_proof"),
+        "Flat".to_string(),
+        flat_id,
+        1u32,
+        None::<ManifestBucket>,
+    )).expect_commit_success();
+    let people = env.people;
+    env.move_in(guest, people, renter, flat_id).expect_commit_success();
+
+    env.call_updater("update_system_object", manifest_args!(flat_id, 1u32, 0u32)).expect_commit_failure();
+    env.call_updater("update_system_object", manifest_args!(flat_id, 1u32, 1u32)).expect_commit_failure();
+    env.call_updater("update_system_object", manifest_args!(flat_id, 1u32, 2u32)).expect_commit_success();
+}
+
+#[test]
+fn test_sold_object_pays_the_whole_price() {
+    let mut env = TestEnv::new();
+    env.add_object_type("Bike", 10, true, OTHER, 0);
+    env.buy_coins(30);
+
+    let account = env.account;
+    env.call_updater("new_object", manifest_args!("Bike".to_string(), account, 0u64))
+        .expect_commit_success();
 
-    // Create an account
-    let (public_key, _private_key, account) = ledger.new_allocated_account();
+    let receipt = env.sell_object(1, 30);
+    let event: SoldObjectEvent = env.event_data(&receipt, "SoldObjectEvent");
+    assert_eq!(event.object_id, 1);
+    assert_eq!(event.price, 30);
+    assert_eq!(event.receipt_id, 1);
 
-    // Publish package
-    let package_address = ledger.compile_and_publish(this_package!());
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, env.coin, dec!(30))
+        .take_all_from_worktop(env.coin, "coins")
+        .call_method_with_name_lookup(env.component, "buy_used_object", |lookup| manifest_args!(
+            1u64,
+            None::<ManifestProof>,
+            lookup.bucket("coins"),
+        ))
+        .call_method(account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+    env.execute(manifest).expect_commit_success();
+    assert_eq!(env.ledger.get_component_balance(account, env.coin), dec!(0));
 
-    // Test the `instantiate_hello` function.
     let manifest = ManifestBuilder::new()
         .lock_fee_from_faucet()
-        .call_function(
-            package_address,
-            "Hello",
-            "instantiate_hello",
-            manifest_args!(),
+        .withdraw_non_fungibles_from_account(account, env.sold_object, [NonFungibleLocalId::integer(1)])
+        .take_all_from_worktop(env.sold_object, "sold_object")
+        .call_method_with_name_lookup(env.component, "close_object_sale", |lookup| (lookup.bucket("sold_object"),))
+        .call_method(account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+    env.execute(manifest).expect_commit_success();
+    assert_eq!(env.ledger.get_component_balance(account, env.coin), dec!(30));
+}
+
+#[test]
+fn test_borrow_and_repay() {
+    let mut env = TestEnv::new();
+    let borrower = env.buy_egg();
+    env.buy_coins(200);
+    env.deposit(borrower, 100).expect_commit_success();
+
+    // Half the locked balance can be borrowed
+    env.call_as_people(borrower, "borrow", None, |lookup| manifest_args!(lookup.proof("people_proof"), 60u32))
+        .expect_commit_failure();
+    env.call_as_people(borrower, "borrow", None, |lookup| manifest_args!(lookup.proof("people_proof"), 40u32))
+        .expect_commit_success();
+    assert_eq!(env.bank_balance(borrower), 20);
+    assert_eq!(env.call_public::<Vec<u64>>("get_loans", manifest_args!(borrower)), vec![1]);
+
+    // 10% interest, the collateral is released with the last installment
+    env.call_as_people(borrower, "repay", Some(30), |lookup| manifest_args!(
+        lookup.proof("people_proof"),
+        1u64,
+        30u32,
+        lookup.bucket("coins"),
+    )).expect_commit_success();
+    assert_eq!(env.bank_balance(borrower), 20);
+    env.call_as_people(borrower, "repay", Some(20), |lookup| manifest_args!(
+        lookup.proof("people_proof"),
+        1u64,
+        20u32,
+        lookup.bucket("coins"),
+    )).expect_commit_success();
+    assert_eq!(env.bank_balance(borrower), 100);
+    assert!(env.call_public::<Vec<u64>>("get_loans", manifest_args!(borrower)).is_empty());
+}
+
+#[test]
+fn test_liquidate_expired_loan() {
+    let mut env = TestEnv::new();
+    let borrower = env.buy_egg();
+    env.buy_coins(100);
+    env.deposit(borrower, 100).expect_commit_success();
+    env.call_as_people(borrower, "borrow", None, |lookup| manifest_args!(lookup.proof("people_proof"), 40u32))
+        .expect_commit_success();
+
+    env.call_public_receipt("liquidate", manifest_args!(1u64)).expect_commit_failure();
+
+    env.advance_days(31);
+    env.call_public_receipt("liquidate", manifest_args!(1u64)).expect_commit_success();
+
+    // The 80 coins collateral pays the 44 outstanding, the rest goes back to the borrower
+    assert_eq!(env.bank_balance(borrower), 56);
+    assert!(env.call_public::<Vec<u64>>("get_loans", manifest_args!(borrower)).is_empty());
+}
+
+#[test]
+fn test_heir_takes_over_loans() {
+    let mut env = TestEnv::new();
+    let borrower = env.buy_egg();
+    let heir = env.buy_egg();
+    env.buy_coins(200);
+    env.deposit(borrower, 100).expect_commit_success();
+    env.call_as_people(borrower, "borrow", None, |lookup| manifest_args!(lookup.proof("people_proof"), 40u32))
+        .expect_commit_success();
+
+    env.call_as_people(borrower, "set_heir", None, |lookup| manifest_args!(lookup.proof("people_proof"), Some(heir)))
+        .expect_commit_success();
+    let receipt = env.call_as_people(borrower, "retire_people", None, |lookup| manifest_args!(lookup.proof("people_proof")));
+    assert!(env.has_event(&receipt, "InheritanceEvent"));
+
+    assert_eq!(env.bank_balance(heir), 20);
+    assert_eq!(env.call_public::<Vec<u64>>("get_loans", manifest_args!(heir)), vec![1]);
+    assert!(env.call_public::<Vec<u64>>("get_loans", manifest_args!(borrower)).is_empty());
+
+    env.call_as_people(borrower, "withdraw_from_bank_account", None, |lookup| manifest_args!(lookup.proof("people_proof"), 1u32))
+        .expect_commit_failure();
+    env.call_as_people(borrower, "repay", Some(44), |lookup| manifest_args!(
+        lookup.proof("people_proof"),
+        1u64,
+        44u32,
+        lookup.bucket("coins"),
+    )).expect_commit_failure();
+
+    env.call_as_people(heir, "repay", Some(44), |lookup| manifest_args!(
+        lookup.proof("people_proof"),
+        1u64,
+        44u32,
+        lookup.bucket("coins"),
+    )).expect_commit_success();
+    assert_eq!(env.bank_balance(heir), 100);
+}
+
+#[test]
+fn test_loans_without_heir_are_liquidated() {
+    let mut env = TestEnv::new();
+    let borrower = env.buy_egg();
+    env.buy_coins(100);
+    env.deposit(borrower, 100).expect_commit_success();
+    env.call_as_people(borrower, "borrow", None, |lookup| manifest_args!(lookup.proof("people_proof"), 40u32))
+        .expect_commit_success();
+
+    let receipt = env.call_as_people(borrower, "retire_people", None, |lookup| manifest_args!(lookup.proof("people_proof")));
+    assert!(env.has_event(&receipt, "LoanLiquidatedEvent"));
+    assert!(env.call_public::<Option<ScryptoValue>>("get_loan", manifest_args!(1u64)).is_none());
+    assert_eq!(env.bank_balance(borrower), 0);
+}
+
+#[test]
+fn test_mortgaged_purchase_opens_loans() {
+    let mut env = TestEnv::new();
+    let owner = env.buy_egg();
+    env.buy_coins(100);
+    env.add_object_type("Bike", 10, true, OTHER, 0);
+
+    // A mortgage needs a borrower
+    env.buy_objects("Bike", 2, true, None, 10).expect_commit_failure();
+
+    let receipt = env.buy_objects("Bike", 2, true, Some(owner), 10);
+    assert!(env.has_event(&receipt, "LoanEvent"));
+    assert_eq!(env.ledger.get_component_balance(env.account, env.coin), dec!(90));
+    assert_eq!(env.ledger.get_component_balance(env.account, env.object), dec!(0));
+    assert_eq!(env.call_public::<Vec<u64>>("get_loans", manifest_args!(owner)), vec![1, 2]);
+    assert_eq!(env.call_public::<Vec<u64>>("get_owned_objects", manifest_args!(owner)), vec![1, 2]);
+
+    // Repaying the 5 coins financed plus interest releases the object
+    env.call_as_people(owner, "repay", Some(6), |lookup| manifest_args!(
+        lookup.proof("people_proof"),
+        1u64,
+        6u32,
+        lookup.bucket("coins"),
+    )).expect_commit_success();
+    assert_eq!(env.ledger.get_component_balance(env.account, env.object), dec!(1));
+
+    // An unpaid mortgage costs the object
+    env.advance_days(31);
+    env.call_public_receipt("liquidate", manifest_args!(2u64)).expect_commit_success();
+    assert_eq!(env.call_public::<Vec<u64>>("get_owned_objects", manifest_args!(owner)), vec![1]);
+}
+
+#[test]
+fn test_no_backdated_interest_on_rent() {
+    let mut env = TestEnv::new();
+    let people_id = env.buy_egg();
+    env.buy_coins(1012);
+    env.call_owner("update_savings_apr", manifest_args!(dec!("0.05"))).expect_commit_success();
+    env.add_object_type("Bike", 10, true, OTHER, 0);
+    env.buy_objects("Bike", 1, false, Some(people_id), 10).expect_commit_success();
+
+    env.deposit(people_id, 1).expect_commit_success();
+    env.advance_days(365);
+
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(env.account, env.object, [NonFungibleLocalId::integer(1)])
+        .pop_from_auth_zone("object_proof")
+        .call_method_with_name_lookup(env.component, "allow_rent", |lookup| manifest_args!(
+            lookup.proof("object_proof"),
+            true,
+            Some(1000u32),
+            None::<u64>,
+        ))
+        .build();
+    env.execute(manifest).expect_commit_success();
+
+    // The rent is credited to the renter itself
+    env.call_as_people(people_id, "rent", Some(1000), |lookup| manifest_args!(
+        lookup.proof("people_proof"),
+        "Bike".to_string(),
+        1u64,
+        1u32,
+        Some(lookup.bucket("coins")),
+    )).expect_commit_success();
+
+    // A year of interest on 1 coin is less than a coin, none is paid on the rent
+    env.deposit(people_id, 1).expect_commit_success();
+    assert_eq!(env.bank_balance(people_id), 1002);
+}
+
+#[test]
+fn test_interest_fractions_add_up() {
+    let mut env = TestEnv::new();
+    let people_id = env.buy_egg();
+    env.buy_coins(3);
+    env.call_owner("update_savings_apr", manifest_args!(dec!(1))).expect_commit_success();
+
+    // Empty deposits don't touch the account
+    env.deposit(people_id, 0).expect_commit_failure();
+
+    // Half a coin, then a quarter of a year on 2 coins: the two halves make a coin
+    env.deposit(people_id, 1).expect_commit_success();
+    env.set_time(env.now + 31536000 / 2);
+    env.deposit(people_id, 1).expect_commit_success();
+    assert_eq!(env.bank_balance(people_id), 2);
+    env.set_time(env.now + 31536000 / 4);
+    env.deposit(people_id, 1).expect_commit_success();
+    assert_eq!(env.bank_balance(people_id), 4);
+}
+
+#[test]
+fn test_reward_call_cap_applies_to_every_payout() {
+    let mut env = TestEnv::new();
+    let account = env.account;
+    env.call_owner("update_reward_caps", manifest_args!(Some(100u32), None::<u32>, None::<u32>))
+        .expect_commit_success();
+
+    env.call_updater("send_coins_batch", manifest_args!(vec![(account, 60u32, None::<u64>), (account, 101u32, None::<u64>)], 1u16))
+        .expect_commit_failure();
+    env.call_updater("send_coins_batch", manifest_args!(vec![(account, 60u32, None::<u64>), (account, 60u32, None::<u64>)], 1u16))
+        .expect_commit_success();
+    assert_eq!(env.ledger.get_component_balance(account, env.coin), dec!(120));
+}
+
+fn set_oracle_price(env: &mut TestEnv, oracle: ComponentAddress, xrd_usd_price: Decimal) {
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(oracle, "set_price", manifest_args!(xrd_usd_price))
+        .build();
+    env.execute(manifest).expect_commit_success();
+}
+
+#[test]
+fn test_oracle_price() {
+    let mut env = TestEnv::new();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(env.mocks_package, "MockOracle", "new", manifest_args!(dec!("0.02")))
+        .build();
+    let oracle = env.execute(manifest).expect_commit_success().new_component_addresses()[0];
+
+    // 0.01 USD per coin, 0.5 XRD at 0.02 USD per XRD, accepted between 0.1 and 10 XRD
+    env.call_owner(
+        "set_price_oracle",
+        manifest_args!(Some(oracle), dec!("0.01"), 3600i64, dec!("0.1"), dec!(10)),
+    ).expect_commit_success();
+    assert_eq!(env.call_public::<Decimal>("get_coin_xrd_price", manifest_args!()), dec!("0.5"));
+
+    // Out of bounds, the stored price is used
+    set_oracle_price(&mut env, oracle, dec!("0.0001"));
+    assert_eq!(env.call_public::<Decimal>("get_coin_xrd_price", manifest_args!()), dec!(1));
+    set_oracle_price(&mut env, oracle, dec!("0.04"));
+    assert_eq!(env.call_public::<Decimal>("get_coin_xrd_price", manifest_args!()), dec!("0.25"));
+
+    // Stale
+    env.advance_days(1);
+    assert_eq!(env.call_public::<Decimal>("get_coin_xrd_price", manifest_args!()), dec!(1));
+
+    // Updated in the future
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_method(oracle, "set_updated", manifest_args!(Instant::new(env.now + 60)))
+        .build();
+    env.execute(manifest).expect_commit_success();
+    assert_eq!(env.call_public::<Decimal>("get_coin_xrd_price", manifest_args!()), dec!(1));
+
+    // Without oracle
+    env.call_owner(
+        "set_price_oracle",
+        manifest_args!(None::<ComponentAddress>, dec!(0), 0i64, dec!(0), dec!(0)),
+    ).expect_commit_success();
+    assert_eq!(env.call_public::<Decimal>("get_coin_xrd_price", manifest_args!()), dec!(1));
+}
+
+#[test]
+fn test_coins_bought_at_oracle_price() {
+    let mut env = TestEnv::new();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(env.mocks_package, "MockOracle", "new", manifest_args!(dec!("0.02")))
+        .build();
+    let oracle = env.execute(manifest).expect_commit_success().new_component_addresses()[0];
+    env.call_owner(
+        "set_price_oracle",
+        manifest_args!(Some(oracle), dec!("0.01"), 3600i64, dec!("0.1"), dec!(10)),
+    ).expect_commit_success();
+
+    env.buy_coins(10);
+    assert_eq!(env.ledger.get_component_balance(env.account, env.coin), dec!(20));
+}
+
+#[test]
+fn test_migrate_people() {
+    let mut env = TestEnv::new();
+    let legacy_people = |father: u64| LegacyPeopleData {
+        name: "Legacy".to_string(),
+        birth_date: Instant::new(0),
+        father: father,
+        mother: 0,
+        gender: "Female".to_string(),
+        occupation: "".to_string(),
+        partner: 0,
+        mood_status: "Sad".to_string(),
+        health_status: "Unknown".to_string(),
+        schooling: "".to_string(),
+        key_image_url: UncheckedUrl::of("https://radixlife.test/people.png"),
+    };
+    let account = env.account;
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_non_fungible_resource(
+            OwnerRole::None,
+            NonFungibleIdType::Integer,
+            false,
+            NonFungibleResourceRoles::default(),
+            metadata!(),
+            Some([
+                (NonFungibleLocalId::integer(1), legacy_people(0)),
+                (NonFungibleLocalId::integer(2), legacy_people(1)),
+            ]),
         )
+        .call_method(account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
         .build();
-    let receipt = ledger.execute_manifest(
-        manifest,
-        vec![NonFungibleGlobalId::from_public_key(&public_key)],
-    );
-    println!("{:?}\n", receipt);
-    let component = receipt.expect_commit(true).new_component_addresses()[0];
+    let legacy = env.execute(manifest).expect_commit_success().new_resource_addresses()[0];
+
+    env.call_owner("set_legacy_people_resource", manifest_args!(legacy, 2u64)).expect_commit_success();
+    env.call_owner("set_legacy_people_resource", manifest_args!(legacy, 2u64)).expect_commit_failure();
 
-    // Test the `free_token` method.
+    env.advance_days(1000);
     let manifest = ManifestBuilder::new()
         .lock_fee_from_faucet()
-        .call_method(component, "free_token", manifest_args!())
-        .call_method(
+        .withdraw_non_fungibles_from_account(
             account,
-            "deposit_batch",
-            manifest_args!(ManifestExpression::EntireWorktop),
+            legacy,
+            [NonFungibleLocalId::integer(1), NonFungibleLocalId::integer(2)],
         )
+        .take_all_from_worktop(legacy, "legacy_people")
+        .call_method_with_name_lookup(env.component, "migrate_people", |lookup| (lookup.bucket("legacy_people"),))
+        .call_method(account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+    env.execute(manifest).expect_commit_success();
+    env.resolve_random(1).expect_commit_success();
+    env.resolve_random(2).expect_commit_success();
+    assert_eq!(env.ledger.get_component_balance(account, env.people), dec!(2));
+    assert_eq!(env.call_public::<Vec<u64>>("get_children", manifest_args!(1u64)), vec![2]);
+
+    // The needs start from the migration, not from the birth
+    let receipt = env.call_public_receipt("refresh_needs", manifest_args!(2u64));
+    let event: NeedsChangedEvent = env.event_data(&receipt, "NeedsChangedEvent");
+    assert_eq!(event.last_meal, env.now);
+    assert_eq!(event.last_rest, env.now);
+    assert_eq!(event.health_status, HealthStatus::Healthy);
+    assert_eq!(event.mood_status, MoodStatus::Happy);
+
+    // New Radixians follow the legacy ids
+    env.last_people_id = 2;
+    assert_eq!(env.buy_egg(), 3);
+}
+
+#[test]
+fn test_legacy_resource_set_before_minting() {
+    let mut env = TestEnv::new();
+    env.buy_egg();
+    let account = env.account;
+    let legacy = env.ledger.create_non_fungible_resource(account);
+
+    env.call_owner("set_legacy_people_resource", manifest_args!(legacy, 2u64)).expect_commit_failure();
+}
+
+#[test]
+fn test_needs_decay() {
+    let mut env = TestEnv::new();
+    let people_id = env.buy_egg();
+    let account = env.account;
+    env.call_owner("add_choice", manifest_args!("work".to_string(), Some(0u32), Vec::<Need>::new(), true, false, false))
+        .expect_commit_success();
+    env.call_owner("add_choice", manifest_args!("eat".to_string(), Some(0u32), vec![Need::Food], false, false, false))
+        .expect_commit_success();
+    env.call_owner("add_choice", manifest_args!("sleep".to_string(), Some(0u32), vec![Need::Rest], false, false, false))
+        .expect_commit_success();
+    let make_choice = |env: &mut TestEnv, choice: &str| env.call_as_people(people_id, "make_choice", None, |lookup| manifest_args!(
+        lookup.proof("people_proof"),
+        choice.to_string(),
+        None::<ManifestBucket>,
+        0u64,
+    ));
+
+    // A new Radixian is already fed and rested
+    let receipt = env.call_public_receipt("refresh_needs", manifest_args!(people_id));
+    assert!(!env.has_event(&receipt, "NeedsChangedEvent"));
+
+    env.advance_days(1);
+    let receipt = env.call_public_receipt("refresh_needs", manifest_args!(people_id));
+    let event: NeedsChangedEvent = env.event_data(&receipt, "NeedsChangedEvent");
+    assert_eq!(event.health_status, HealthStatus::Weak);
+    assert_eq!(event.mood_status, MoodStatus::Normal);
+    make_choice(&mut env, "work").expect_commit_success();
+
+    // A sick Radixian can neither work nor breed
+    env.advance_days(1);
+    make_choice(&mut env, "work").expect_commit_failure();
+    env.call_updater("new_egg", manifest_args!(people_id, 0u64, account)).expect_commit_failure();
+
+    let receipt = make_choice(&mut env, "eat");
+    let event: NeedsChangedEvent = env.event_data(&receipt, "NeedsChangedEvent");
+    assert_eq!(event.last_meal, env.now);
+    assert_eq!(event.health_status, HealthStatus::Healthy);
+    assert_eq!(event.mood_status, MoodStatus::Sad);
+    let receipt = make_choice(&mut env, "sleep");
+    let event: NeedsChangedEvent = env.event_data(&receipt, "NeedsChangedEvent");
+    assert_eq!(event.mood_status, MoodStatus::Happy);
+    make_choice(&mut env, "work").expect_commit_success();
+    env.call_updater("new_egg", manifest_args!(people_id, 0u64, account)).expect_commit_success();
+}
+
+#[test]
+fn test_child_genome_inheritance() {
+    let mut env = TestEnv::new();
+    let account = env.account;
+    // Founders get the first bytes of the seed
+    let father = env.buy_egg();
+    let mother = env.buy_egg();
+
+    // No mutations, an even selector takes every gene from the father and an odd one from the mother
+    env.call_updater("new_egg", manifest_args!(father, mother, account)).expect_commit_success();
+    let receipt = env.resolve_random(10);
+    let event: GenomeEvent = env.event_data(&receipt, "GenomeEvent");
+    assert_eq!(event.key, 3);
+    assert_eq!(event.people_id, 3);
+    assert_eq!(event.seed, vec![10u8; 32]);
+    assert_eq!(event.genome, vec![1u8; 16]);
+
+    env.call_updater("new_egg", manifest_args!(father, mother, account)).expect_commit_success();
+    let receipt = env.resolve_random(11);
+    let event: GenomeEvent = env.event_data(&receipt, "GenomeEvent");
+    assert_eq!(event.key, 4);
+    assert_eq!(event.people_id, 4);
+    assert_eq!(event.seed, vec![11u8; 32]);
+    assert_eq!(event.genome, vec![2u8; 16]);
+
+    // A parent still waiting for its genome can't breed
+    env.call_updater("new_egg", manifest_args!(0u64, 0u64, account)).expect_commit_success();
+    env.call_updater("new_egg", manifest_args!(5u64, mother, account)).expect_commit_failure();
+}
+
+#[test]
+fn test_list_object_types() {
+    let mut env = TestEnv::new();
+    let receipt = env.call_owner(
+        "add_object_type",
+        manifest_args!(
+            "Flat".to_string(),
+            100u32,
+            "https://radixlife.test/flat.png".to_string(),
+            true,
+            false,
+            true,
+            Some(10u32),
+            5u8,
+            category(HOUSE),
+            3u32,
+        ),
+    );
+    let event: ObjectTypeAddedEvent = env.event_data(&receipt, "ObjectTypeAddedEvent");
+    assert_eq!(event.name, "Flat");
+    assert_eq!(event.price, 100);
+    assert_eq!(event.key_image_url, "https://radixlife.test/flat.png");
+    assert!(event.can_be_bought && !event.can_be_mortgaged && event.can_be_rent);
+    assert_eq!(event.max_supply, Some(10));
+    assert_eq!(event.daily_decay, 5);
+    assert_eq!(event.category, ObjectCategory::House);
+    assert_eq!(event.capacity, 3);
+
+    env.add_object_type("Bike", 10, false, OTHER, 0);
+    env.add_object_type("Hammer", 5, false, TOOL, 0);
+    // Names are unique
+    env.call_owner(
+        "add_object_type",
+        manifest_args!(
+            "Bike".to_string(),
+            20u32,
+            "https://radixlife.test/object.png".to_string(),
+            true,
+            true,
+            false,
+            None::<u32>,
+            0u8,
+            category(VEHICLE),
+            0u32,
+        ),
+    ).expect_commit_failure();
+
+    let object_types: Vec<(String, ObjectTypeData)> = env.call_public("list_object_types", manifest_args!(0u32, 10u32));
+    let names: Vec<&str> = object_types.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["Flat", "Bike", "Hammer"]);
+    let (_, bike) = &object_types[1];
+    assert_eq!(bike.price, 10);
+    assert_eq!(bike.key_image_url, UncheckedUrl::of("https://radixlife.test/object.png"));
+    assert!(bike.can_be_bought && bike.can_be_mortgaged && !bike.can_be_rent);
+    assert_eq!(bike.max_supply, None);
+    assert_eq!(bike.minted, 0);
+    assert!(!bike.retired);
+    assert_eq!(bike.daily_decay, 0);
+    assert_eq!(bike.category, ObjectCategory::Other);
+    assert_eq!(bike.capacity, 0);
+    assert_eq!(object_types[2].1.category, ObjectCategory::Tool);
+
+    let page: Vec<(String, ObjectTypeData)> = env.call_public("list_object_types", manifest_args!(1u32, 1u32));
+    assert_eq!(page.len(), 1);
+    assert_eq!(page[0].0, "Bike");
+    assert!(env.call_public::<Vec<(String, ObjectTypeData)>>("list_object_types", manifest_args!(3u32, 10u32)).is_empty());
+}
+
+#[test]
+fn test_epoch_and_daily_reward_caps() {
+    let mut env = TestEnv::new();
+    let account = env.account;
+    env.call_owner("update_reward_caps", manifest_args!(None::<u32>, Some(100u32), Some(150u32)))
+        .expect_commit_success();
+    let send_coins = |env: &mut TestEnv, amount: u32| env.call_updater(
+        "send_coins",
+        manifest_args!(amount, account, 1u16, None::<u64>),
+    );
+
+    send_coins(&mut env, 60).expect_commit_success();
+    send_coins(&mut env, 50).expect_commit_failure();
+
+    // A new epoch, but the same day
+    let epoch = env.ledger.get_current_epoch();
+    env.ledger.set_current_epoch(epoch.next().unwrap());
+    send_coins(&mut env, 50).expect_commit_success();
+    send_coins(&mut env, 50).expect_commit_failure();
+    send_coins(&mut env, 40).expect_commit_success();
+
+    env.advance_days(1);
+    env.ledger.set_current_epoch(epoch.next().unwrap().next().unwrap());
+    send_coins(&mut env, 100).expect_commit_success();
+    assert_eq!(env.ledger.get_component_balance(account, env.coin), dec!(250));
+}
+
+#[test]
+fn test_rewarded_people_in_batch() {
+    let mut env = TestEnv::new();
+    let account = env.account;
+    let people_id = env.buy_egg();
+
+    env.call_updater("send_coins_batch", manifest_args!(vec![(account, 10u32, Some(99u64))], 2u16))
+        .expect_commit_failure();
+    let receipt = env.call_updater(
+        "send_coins_batch",
+        manifest_args!(vec![(account, 10u32, Some(people_id)), (account, 5u32, None::<u64>)], 2u16),
+    );
+    let event: CoinsSentEvent = env.event_data(&receipt, "CoinsSentEvent");
+    assert_eq!(event.account, account);
+    assert_eq!(event.amount, 10);
+    assert_eq!(event.reason, 2);
+    assert_eq!(event.people_id, people_id);
+    assert_eq!(env.ledger.get_component_balance(account, env.coin), dec!(15));
+}
+
+#[test]
+fn test_object_owner() {
+    let mut env = TestEnv::new();
+    let first = env.buy_egg();
+    let second = env.buy_egg();
+    env.buy_coins(20);
+    env.add_object_type("Bike", 10, true, OTHER, 0);
+
+    // The owner is the Radixian whose proof is presented
+    env.buy_objects("Bike", 1, false, Some(first), 10).expect_commit_success();
+    assert_eq!(env.call_public::<u64>("get_object_owner", manifest_args!(1u64)), first);
+    assert_eq!(env.call_public::<Vec<u64>>("get_owned_objects", manifest_args!(first)), vec![1]);
+    env.buy_objects("Bike", 1, false, None, 10).expect_commit_success();
+    assert_eq!(env.call_public::<u64>("get_object_owner", manifest_args!(2u64)), 0);
+
+    let transfer = |env: &mut TestEnv, owner_resource: ResourceAddress, owner: Option<u64>| {
+        let mut builder = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_non_fungibles(env.account, env.object, [NonFungibleLocalId::integer(1)])
+            .pop_from_auth_zone("object_proof");
+        if let Some(owner) = owner {
+            builder = builder
+                .create_proof_from_account_of_non_fungibles(env.account, owner_resource, [NonFungibleLocalId::integer(owner)])
+                .pop_from_auth_zone("owner_proof");
+        }
+        let manifest = builder
+            .call_method_with_name_lookup(env.component, "transfer_object_owner", |lookup| manifest_args!(
+                lookup.proof("object_proof"),
+                owner.map(|_| lookup.proof("owner_proof")),
+            ))
+            .build();
+        env.execute(manifest)
+    };
+
+    // An object can't own an object
+    let object = env.object;
+    transfer(&mut env, object, Some(2)).expect_commit_failure();
+
+    let people = env.people;
+    let receipt = transfer(&mut env, people, Some(second));
+    assert!(env.has_event(&receipt, "ObjectOwnerEvent"));
+    assert_eq!(env.call_public::<u64>("get_object_owner", manifest_args!(1u64)), second);
+    assert!(env.call_public::<Vec<u64>>("get_owned_objects", manifest_args!(first)).is_empty());
+    assert_eq!(env.call_public::<Vec<u64>>("get_owned_objects", manifest_args!(second)), vec![1]);
+
+    transfer(&mut env, people, None).expect_commit_success();
+    assert_eq!(env.call_public::<u64>("get_object_owner", manifest_args!(1u64)), 0);
+    assert!(env.call_public::<Vec<u64>>("get_owned_objects", manifest_args!(second)).is_empty());
+
+    // A retired Radixian can't own anything new
+    env.call_updater("execute_inheritance", manifest_args!(first)).expect_commit_success();
+    transfer(&mut env, people, Some(first)).expect_commit_failure();
+}
+
+#[test]
+fn test_people_fields() {
+    let mut env = TestEnv::new();
+    let people_id = env.buy_egg();
+    let update = |env: &mut TestEnv, field: &str, value: &str| env.call_updater(
+        "update_people_data",
+        manifest_args!(
+            people_id,
+            Some(BTreeMap::from([(field.to_string(), value.to_string())])),
+            None::<u64>,
+            None::<String>,
+        ),
+    );
+
+    let receipt = update(&mut env, "gender", "female");
+    let event: PeopleUpdatedEvent = env.event_data(&receipt, "PeopleUpdatedEvent");
+    assert_eq!(event.people_id, people_id);
+    assert_eq!(event.changes.len(), 1);
+    assert_eq!(event.changes[0].field, "gender");
+    assert_eq!(event.changes[0].old_value, "unknown");
+    assert_eq!(event.changes[0].new_value, "female");
+    update(&mut env, "gender", "robot").expect_commit_failure();
+    update(&mut env, "name", "Bad#name").expect_commit_failure();
+
+    // Only the whitelisted fields can be updated
+    update(&mut env, "birth_date", "0").expect_commit_failure();
+    update(&mut env, "health_status", "sick").expect_commit_failure();
+    update(&mut env, "mood_status", "sad").expect_commit_failure();
+    env.call_owner("set_people_field_domain", manifest_args!("mood_status".to_string(), Some(vec!["sad".to_string()])))
+        .expect_commit_failure();
+
+    // The owner can restrict the values of a field
+    env.call_owner("set_people_field_domain", manifest_args!("occupation".to_string(), Some(Vec::<String>::new())))
+        .expect_commit_failure();
+    env.call_owner(
+        "set_people_field_domain",
+        manifest_args!("occupation".to_string(), Some(vec!["farmer".to_string(), "miner".to_string()])),
+    ).expect_commit_success();
+    update(&mut env, "occupation", "pilot").expect_commit_failure();
+    update(&mut env, "occupation", "miner").expect_commit_success();
+    env.call_owner("set_people_field_domain", manifest_args!("occupation".to_string(), None::<Vec<String>>))
+        .expect_commit_success();
+    update(&mut env, "occupation", "pilot").expect_commit_success();
+
+    // A Radixian can't be its own partner
+    env.call_updater("update_people_data", manifest_args!(people_id, None::<BTreeMap<String, String>>, Some(people_id), None::<String>))
+        .expect_commit_failure();
+}
+
+#[test]
+fn test_family_tree() {
+    let mut env = TestEnv::new();
+    let account = env.account;
+    let breed = |env: &mut TestEnv, father: u64, mother: u64| {
+        env.call_updater("new_egg", manifest_args!(father, mother, account)).expect_commit_success();
+        env.resolve_random(10).expect_commit_success();
+        env.last_people_id += 1;
+        env.last_people_id
+    };
+    let founders: Vec<u64> = (0..4).map(|_| env.buy_egg()).collect();
+    let first_child = breed(&mut env, founders[0], founders[1]);
+    let second_child = breed(&mut env, founders[2], founders[3]);
+    let grandchild = breed(&mut env, first_child, second_child);
+
+    assert_eq!(env.call_public::<Vec<u64>>("get_children", manifest_args!(founders[0])), vec![first_child]);
+    assert_eq!(env.call_public::<Vec<u64>>("get_ancestors", manifest_args!(grandchild, 1u8)), vec![first_child, second_child]);
+    assert_eq!(
+        env.call_public::<Vec<u64>>("get_ancestors", manifest_args!(grandchild, 2u8)),
+        vec![first_child, second_child, founders[0], founders[1], founders[2], founders[3]],
+    );
+    assert_eq!(env.call_public::<Vec<u64>>("get_descendants", manifest_args!(founders[0], 2u8)), vec![first_child, grandchild]);
+
+    // The parents must exist and be different
+    env.call_updater("new_egg", manifest_args!(founders[0], founders[0], account)).expect_commit_failure();
+    env.call_updater("new_egg", manifest_args!(founders[0], 99u64, account)).expect_commit_failure();
+
+    // No breeding with ancestors or siblings
+    env.call_updater("new_egg", manifest_args!(first_child, founders[1], account)).expect_commit_failure();
+    let sibling = breed(&mut env, founders[0], founders[1]);
+    env.call_updater("new_egg", manifest_args!(first_child, sibling, account)).expect_commit_failure();
+
+    // Only the configured number of generations is checked
+    env.call_updater("new_egg", manifest_args!(grandchild, founders[2], account)).expect_commit_failure();
+    env.call_owner("update_inbreeding_generations", manifest_args!(1u8)).expect_commit_success();
+    env.call_updater("new_egg", manifest_args!(grandchild, founders[2], account)).expect_commit_success();
+    env.call_updater("new_egg", manifest_args!(first_child, sibling, account)).expect_commit_failure();
+    env.call_owner("update_inbreeding_generations", manifest_args!(0u8)).expect_commit_success();
+    env.call_updater("new_egg", manifest_args!(first_child, sibling, account)).expect_commit_success();
+}
+
+#[test]
+fn test_limited_edition() {
+    let mut env = TestEnv::new();
+    let people_id = env.buy_egg();
+    env.buy_coins(100);
+    env.call_owner(
+        "add_object_type",
+        manifest_args!(
+            "Statue".to_string(),
+            10u32,
+            "https://radixlife.test/statue.png".to_string(),
+            true,
+            false,
+            false,
+            Some(3u32),
+            0u8,
+            category(OTHER),
+            0u32,
+        ),
+    ).expect_commit_success();
+
+    let receipt = env.buy_objects("Statue", 2, false, Some(people_id), 20);
+    let event: NewObjectEvent = env.event_data(&receipt, "NewObjectEvent");
+    assert_eq!(event.name, "Statue");
+    assert_eq!(event.ids, vec![1, 2]);
+    assert_eq!(event.first_edition, 1);
+    assert_eq!(event.owner, people_id);
+
+    env.buy_objects("Statue", 2, false, None, 20).expect_commit_failure();
+    env.buy_objects("Statue", 1, false, None, 10).expect_commit_success();
+    let object_data = env.object_data(3);
+    assert_eq!(object_data.name, "Statue");
+    assert_eq!(object_data.edition, 3);
+    assert_eq!(object_data.max_supply, Some(3));
+
+    // Sold out, whatever the way of minting
+    env.buy_objects("Statue", 1, false, None, 10).expect_commit_failure();
+    let account = env.account;
+    env.call_updater("new_object", manifest_args!("Statue".to_string(), account, 0u64)).expect_commit_failure();
+    let object_type: ObjectTypeData = env.call_public::<Option<ObjectTypeData>>("get_object_type", manifest_args!("Statue".to_string())).unwrap();
+    assert_eq!(object_type.minted, 3);
+}
+
+#[test]
+fn test_object_type_image_and_retirement() {
+    let mut env = TestEnv::new();
+    env.buy_coins(100);
+    env.add_object_type("Bike", 10, false, OTHER, 0);
+    env.add_object_type("Car", 20, false, VEHICLE, 0);
+    env.buy_objects("Bike", 2, false, None, 20).expect_commit_success();
+    env.buy_objects("Car", 1, false, None, 20).expect_commit_success();
+
+    // The new image only goes to the listed objects
+    let new_image = "https://radixlife.test/bike.png".to_string();
+    env.call_owner("update_object_type_image", manifest_args!("Bike".to_string(), new_image.clone(), vec![3u64]))
+        .expect_commit_failure();
+    let receipt = env.call_owner("update_object_type_image", manifest_args!("Bike".to_string(), new_image.clone(), vec![1u64]));
+    let event: ObjectTypeUpdatedEvent = env.event_data(&receipt, "ObjectTypeUpdatedEvent");
+    assert_eq!(event.name, "Bike");
+    assert_eq!(event.key_image_url, new_image);
+    assert_eq!(event.updated_object_ids, vec![1]);
+    assert_eq!(env.object_data(1).key_image_url, UncheckedUrl::of(new_image.clone()));
+    assert_eq!(env.object_data(2).key_image_url, UncheckedUrl::of("https://radixlife.test/object.png"));
+    let object_type = env.call_public::<Option<ObjectTypeData>>("get_object_type", manifest_args!("Bike".to_string())).unwrap();
+    assert_eq!(object_type.key_image_url, UncheckedUrl::of(new_image));
+
+    let receipt = env.call_owner("retire_object_type", manifest_args!("Bike".to_string()));
+    let event: ObjectTypeUpdatedEvent = env.event_data(&receipt, "ObjectTypeUpdatedEvent");
+    assert!(event.retired);
+    assert!(!event.can_be_bought);
+    assert_eq!(event.price, 10);
+    assert!(event.can_be_mortgaged && !event.can_be_rent);
+    assert!(event.updated_object_ids.is_empty());
+    env.call_owner("retire_object_type", manifest_args!("Bike".to_string())).expect_commit_failure();
+
+    // No new objects, the existing ones can still be sold
+    env.buy_objects("Bike", 1, false, None, 10).expect_commit_failure();
+    let account = env.account;
+    env.call_updater("new_object", manifest_args!("Bike".to_string(), account, 0u64)).expect_commit_failure();
+    env.sell_object(1, 5).expect_commit_success();
+}
+
+#[test]
+fn test_decay_and_repair() {
+    let mut env = TestEnv::new();
+    let renter = env.buy_egg();
+    env.buy_coins(115);
+    env.call_owner(
+        "add_object_type",
+        manifest_args!(
+            "Bike".to_string(),
+            50u32,
+            "https://radixlife.test/bike.png".to_string(),
+            true,
+            true,
+            true,
+            None::<u32>,
+            10u8,
+            category(VEHICLE),
+            0u32,
+        ),
+    ).expect_commit_success();
+    env.buy_objects("Bike", 1, false, None, 50).expect_commit_success();
+    let repair = |env: &mut TestEnv, coins: u32| {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_non_fungibles(env.account, env.object, [NonFungibleLocalId::integer(1)])
+            .pop_from_auth_zone("object_proof")
+            .withdraw_from_account(env.account, env.coin, Decimal::from(coins))
+            .take_all_from_worktop(env.coin, "coins")
+            .call_method_with_name_lookup(env.component, "repair", |lookup| manifest_args!(
+                lookup.proof("object_proof"),
+                lookup.bucket("coins"),
+            ))
+            .call_method(env.account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
+            .build();
+        env.execute(manifest)
+    };
+
+    repair(&mut env, 10).expect_commit_failure();
+
+    // Only whole days count, the repair costs the missing percentage of the price
+    env.set_time(env.now + 3 * 86400 + 3600);
+    let receipt = repair(&mut env, 20);
+    let event: ObjectConditionEvent = env.event_data(&receipt, "ObjectConditionEvent");
+    assert_eq!(event.object_id, 1);
+    assert_eq!(event.condition, 70);
+    let event: RepairEvent = env.event_data(&receipt, "RepairEvent");
+    assert_eq!(event.object_id, 1);
+    assert_eq!(event.cost, 15);
+    assert_eq!(env.object_data(1).condition, 100);
+    assert_eq!(env.ledger.get_component_balance(env.account, env.coin), dec!(50));
+
+    // A broken object can't be rent until it's repaired
+    env.advance_days(20);
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_proof_from_account_of_non_fungibles(env.account, env.object, [NonFungibleLocalId::integer(1)])
+        .pop_from_auth_zone("object_proof")
+        .call_method_with_name_lookup(env.component, "allow_rent", |lookup| manifest_args!(
+            lookup.proof("object_proof"),
+            true,
+            Some(0u32),
+            Some(renter),
+        ))
         .build();
-    let receipt = ledger.execute_manifest(
-        manifest,
-        vec![NonFungibleGlobalId::from_public_key(&public_key)],
+    env.execute(manifest).expect_commit_success();
+    let rent = |env: &mut TestEnv| env.call_as_people(renter, "rent", None, |lookup| manifest_args!(
+        lookup.proof("people_proof"),
+        "Bike".to_string(),
+        1u64,
+        1u32,
+        None::<ManifestBucket>,
+    ));
+    rent(&mut env).expect_commit_failure();
+    repair(&mut env, 50).expect_commit_success();
+    assert_eq!(env.ledger.get_component_balance(env.account, env.coin), dec!(0));
+    rent(&mut env).expect_commit_success();
+}
+
+#[test]
+fn test_craft() {
+    let mut env = TestEnv::new();
+    let people_id = env.buy_egg();
+    env.buy_coins(30);
+    env.add_object_type("Wheel", 5, false, OTHER, 0);
+    env.add_object_type("Frame", 10, false, OTHER, 0);
+    env.add_object_type("Bike", 50, false, VEHICLE, 0);
+    env.buy_objects("Wheel", 3, false, Some(people_id), 15).expect_commit_success();
+    env.buy_objects("Frame", 1, false, Some(people_id), 10).expect_commit_success();
+
+    env.call_owner(
+        "add_recipe",
+        manifest_args!("bike".to_string(), vec![("Wheel".to_string(), 2u32)], Some(5u32), "Car".to_string()),
+    ).expect_commit_failure();
+    env.call_owner(
+        "add_recipe",
+        manifest_args!(
+            "bike".to_string(),
+            vec![("Wheel".to_string(), 1u32), ("Frame".to_string(), 1u32), ("Wheel".to_string(), 1u32)],
+            Some(5u32),
+            "Bike".to_string(),
+        ),
+    ).expect_commit_success();
+
+    let craft = |env: &mut TestEnv, object_ids: Vec<u64>| {
+        let people = env.people;
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_non_fungibles(env.account, people, [NonFungibleLocalId::integer(people_id)])
+            .pop_from_auth_zone("owner_proof")
+            .withdraw_non_fungibles_from_account(env.account, env.object, object_ids.into_iter().map(NonFungibleLocalId::integer))
+            .take_all_from_worktop(env.object, "objects")
+            .withdraw_from_account(env.account, env.coin, dec!(5))
+            .take_all_from_worktop(env.coin, "coins")
+            .call_method_with_name_lookup(env.component, "craft", |lookup| manifest_args!(
+                "bike".to_string(),
+                lookup.bucket("objects"),
+                lookup.bucket("coins"),
+                Some(lookup.proof("owner_proof")),
+            ))
+            .call_method(env.account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
+            .build();
+        env.execute(manifest)
+    };
+
+    // Exactly the required objects
+    craft(&mut env, vec![1, 4]).expect_commit_failure();
+    craft(&mut env, vec![1, 2, 3, 4]).expect_commit_failure();
+    let receipt = craft(&mut env, vec![1, 4, 2]);
+    let event: CraftedEvent = env.event_data(&receipt, "CraftedEvent");
+    assert_eq!(event.recipe, "bike");
+    assert_eq!(event.input_ids, vec![1, 4, 2]);
+    assert_eq!(event.output_ids, vec![5]);
+    assert_eq!(event.price, 5);
+    assert_eq!(event.owner, people_id);
+    assert_eq!(env.object_data(5).name, "Bike");
+    assert_eq!(env.call_public::<Vec<u64>>("get_owned_objects", manifest_args!(people_id)), vec![3, 5]);
+    assert_eq!(env.ledger.get_component_balance(env.account, env.object), dec!(2));
+    assert_eq!(env.ledger.get_component_balance(env.account, env.coin), dec!(0));
+}
+
+#[test]
+fn test_house_capacity() {
+    let mut env = TestEnv::new();
+    let residents: Vec<u64> = (0..3).map(|_| env.buy_egg()).collect();
+    env.buy_coins(110);
+    env.add_object_type("House", 100, false, HOUSE, 2);
+    env.add_object_type("Bike", 10, false, OTHER, 0);
+    env.buy_objects("House", 1, false, None, 100).expect_commit_success();
+    env.buy_objects("Bike", 1, false, None, 10).expect_commit_success();
+    let object = env.object;
+
+    env.move_in(residents[0], object, 2, 2).expect_commit_failure();
+    env.move_in(residents[0], object, 2, 1).expect_commit_failure();
+    env.move_in(residents[0], object, 1, 1).expect_commit_success();
+    env.move_in(residents[1], object, 1, 1).expect_commit_success();
+    env.move_in(residents[2], object, 1, 1).expect_commit_failure();
+    assert_eq!(env.call_public::<Vec<u64>>("get_residents", manifest_args!(1u64)), vec![residents[0], residents[1]]);
+
+    // An inhabited house can't be sold
+    env.sell_object(1, 50).expect_commit_failure();
+
+    env.call_as_people(residents[0], "move_out", None, |lookup| manifest_args!(lookup.proof("people_proof")))
+        .expect_commit_success();
+    env.move_in(residents[2], object, 1, 1).expect_commit_success();
+    assert_eq!(env.call_public::<Vec<u64>>("get_residents", manifest_args!(1u64)), vec![residents[1], residents[2]]);
+}
+
+#[test]
+fn test_bank_transfer() {
+    let mut env = TestEnv::new();
+    let sender = env.buy_egg();
+    let receiver = env.buy_egg();
+    env.buy_coins(50);
+    env.deposit(sender, 50).expect_commit_success();
+    let transfer = |env: &mut TestEnv, to_people_id: u64, amount: u32| env.call_as_people(
+        sender,
+        "bank_transfer",
+        None,
+        |lookup| manifest_args!(lookup.proof("people_proof"), to_people_id, amount),
     );
-    println!("{:?}\n", receipt);
-    receipt.expect_commit_success();
+
+    let receipt = transfer(&mut env, receiver, 20);
+    let event: BankTransferEvent = env.event_data(&receipt, "BankTransferEvent");
+    assert_eq!(event.from_people_id, sender);
+    assert_eq!(event.to_people_id, receiver);
+    assert_eq!(event.amount, 20);
+    assert_eq!(env.bank_balance(sender), 30);
+    assert_eq!(env.bank_balance(receiver), 20);
+
+    transfer(&mut env, sender, 10).expect_commit_failure();
+    transfer(&mut env, receiver, 0).expect_commit_failure();
+    transfer(&mut env, receiver, 31).expect_commit_failure();
+    transfer(&mut env, 99, 10).expect_commit_failure();
+
+    env.call_updater("execute_inheritance", manifest_args!(receiver)).expect_commit_success();
+    transfer(&mut env, receiver, 10).expect_commit_failure();
+    assert_eq!(env.bank_balance(sender), 30);
 }
 
 #[test]
-fn test_hello_with_test_environment() -> Result<(), RuntimeError> {
-    // Arrange
-    let mut env = TestEnvironment::new();
-    let package_address = 
-        PackageFactory::compile_and_publish(this_package!(), &mut env, CompileProfile::Fast)?;
+fn test_coin_supply() {
+    let mut env = TestEnv::new();
+    let people_id = env.buy_egg();
+    let account = env.account;
+    env.buy_coins(100);
+    env.deposit(people_id, 40).expect_commit_success();
+    env.add_object_type("Bike", 10, false, OTHER, 0);
+    env.buy_objects("Bike", 1, false, None, 10).expect_commit_success();
+    env.call_updater("send_coins", manifest_args!(5u32, account, 1u16, None::<u64>)).expect_commit_success();
+    env.call_as_people(people_id, "withdraw_from_bank_account", None, |lookup| manifest_args!(lookup.proof("people_proof"), 15u32))
+        .expect_commit_success();
 
-    let mut hello = Hello::instantiate_hello(package_address, &mut env)?;
+    // Every source is listed, even when nothing was minted or burned
+    let supply: CoinSupply = env.call_public("get_coin_supply", manifest_args!());
+    assert_eq!(supply.minted.len(), 10);
+    assert_eq!(supply.burned.len(), 10);
+    let amount = |amounts: &Vec<(CoinSource, Decimal)>, source: CoinSource| amounts.iter()
+        .find(|(amount_source, _)| *amount_source == source)
+        .map(|(_, amount)| *amount)
+        .unwrap();
+    assert_eq!(amount(&supply.minted, CoinSource::Xrd), dec!(100));
+    assert_eq!(amount(&supply.minted, CoinSource::Reward), dec!(5));
+    assert_eq!(amount(&supply.minted, CoinSource::Bank), dec!(15));
+    assert_eq!(amount(&supply.minted, CoinSource::Loan), dec!(0));
+    assert_eq!(amount(&supply.burned, CoinSource::Bank), dec!(40));
+    assert_eq!(amount(&supply.burned, CoinSource::Object), dec!(10));
+    assert_eq!(amount(&supply.burned, CoinSource::Market), dec!(0));
+    assert_eq!(supply.circulating, dec!(70));
+    assert_eq!(env.ledger.get_component_balance(account, env.coin), dec!(70));
+}
 
-    // Act
-    let bucket = hello.free_token(&mut env)?;
+#[test]
+fn test_objects_paid_in_xrd() {
+    let mut env = TestEnv::new();
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .call_function(env.mocks_package, "MockOracle", "new", manifest_args!(dec!("0.02")))
+        .build();
+    let oracle = env.execute(manifest).expect_commit_success().new_component_addresses()[0];
+    env.call_owner(
+        "set_price_oracle",
+        manifest_args!(Some(oracle), dec!("0.01"), 3600i64, dec!("0.1"), dec!(10)),
+    ).expect_commit_success();
+    env.add_object_type("Bike", 10, false, OTHER, 0);
 
-    // Assert
-    let amount = bucket.amount(&mut env)?;
-    assert_eq!(amount, dec!("1"));
+    // 10 coins at 0.5 XRD each, the change comes back in XRD
+    let account = env.account;
+    let xrd_before = env.ledger.get_component_balance(account, XRD);
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .withdraw_from_account(account, XRD, dec!(20))
+        .take_all_from_worktop(XRD, "xrd")
+        .call_method_with_name_lookup(env.component, "buy_objects", |lookup| manifest_args!(
+            lookup.bucket("xrd"),
+            "Bike".to_string(),
+            1u8,
+            false,
+            None::<ManifestProof>,
+        ))
+        .call_method(account, "deposit_batch", manifest_args!(ManifestExpression::EntireWorktop))
+        .build();
+    env.execute(manifest).expect_commit_success();
+    assert_eq!(env.ledger.get_component_balance(account, XRD), xrd_before - dec!(5));
+    assert_eq!(env.ledger.get_component_balance(env.component, XRD), dec!(5));
+    assert_eq!(env.ledger.get_component_balance(account, env.object), dec!(1));
+    assert_eq!(env.ledger.get_component_balance(account, env.coin), dec!(0));
 
-    Ok(())
+    // No coins are burned when paying in XRD
+    let supply: CoinSupply = env.call_public("get_coin_supply", manifest_args!());
+    assert!(supply.burned.iter().all(|(_, amount)| amount.is_zero()));
+    assert_eq!(supply.circulating, dec!(0));
 }
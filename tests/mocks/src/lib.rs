@@ -0,0 +1,2 @@
+pub mod mock_oracle;
+pub mod mock_random;
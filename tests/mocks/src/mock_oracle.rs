@@ -0,0 +1,47 @@
+use scrypto::prelude::*;
+
+// Stand-in for the price oracle: anybody can change the price, the update time is the current one
+#[blueprint]
+mod mock_oracle {
+
+    struct MockOracle {
+        xrd_usd_price: Decimal,
+        updated: Instant,
+    }
+
+    impl MockOracle {
+
+        pub fn new(
+            xrd_usd_price: Decimal,
+        ) -> Global<MockOracle> {
+            Self {
+                xrd_usd_price: xrd_usd_price,
+                updated: Clock::current_time_rounded_to_seconds(),
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        pub fn set_price(
+            &mut self,
+            xrd_usd_price: Decimal,
+        ) {
+            self.xrd_usd_price = xrd_usd_price;
+            self.updated = Clock::current_time_rounded_to_seconds();
+        }
+
+        // Lets the tests simulate a clock mismatch with the oracle
+        pub fn set_updated(
+            &mut self,
+            updated: Instant,
+        ) {
+            self.updated = updated;
+        }
+
+        // Price of one XRD in USD and the time it was last updated
+        pub fn get_price(&self) -> (Decimal, Instant) {
+            (self.xrd_usd_price, self.updated)
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use scrypto::prelude::*;
+
+// Stand-in for the randomness component: requests are queued and resolved when the test calls
+// execute with the seed of its choice
+#[blueprint]
+mod mock_random {
+
+    struct MockRandom {
+        requests: Vec<(ComponentAddress, String, u32)>,
+    }
+
+    impl MockRandom {
+
+        pub fn new() -> Global<MockRandom> {
+            Self {
+                requests: vec![],
+            }
+            .instantiate()
+            .prepare_to_globalize(OwnerRole::None)
+            .globalize()
+        }
+
+        pub fn request_random(
+            &mut self,
+            address: ComponentAddress,
+            method_name: String,
+            key: u32,
+        ) {
+            self.requests.push((address, method_name, key));
+        }
+
+        pub fn execute(
+            &mut self,
+            seed: Vec<u8>,
+        ) {
+            for (address, method_name, key) in self.requests.drain(..) {
+                Global::<AnyComponent>::from(address).call_raw::<()>(
+                    &method_name,
+                    scrypto_args!(key, seed.clone()),
+                );
+            }
+        }
+    }
+}